@@ -1,32 +1,1322 @@
-use anyhow::Result;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    pin::Pin,
+    sync::Mutex,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
 use async_trait::async_trait;
-use zavora_tools::{CommitmentTool, InventoryTool, MessagingTool};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use futures_util::{FutureExt, Stream, StreamExt};
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+use uuid::Uuid;
+use zavora_core::{
+    A2AMessage, DomainEvent, DomainEventKind, EventStore, ReplenishmentNegotiationFailed,
+    ReplenishmentRequest, ReplenishmentResponse,
+};
+use zavora_platform::{OrderCreatedEvent, RedisBus};
+use zavora_tools::{
+    AuditTool, CollectionStage, CommitmentTool, CustomerTool, InventoryTool, LeadTool,
+    MessagingTool, OrderLine, OrderTool, OutOfStockEvent, PeriodCloseLog, PeriodCloseStepOutcome,
+    PeriodCloseTool, ReceivablesTool, Reservation, SupplierCommitment,
+};
+
+const PROCUREMENT_AGENT_ID: &str = "procurement-agent";
+const WAREHOUSE_AGENT_ID: &str = "warehouse-agent";
+const ORDERS_CREATED_CHANNEL: &str = "orders.created";
+const AR_COLLECTION_BREACH: &str = "AR_COLLECTION_BREACH";
+const PERIOD_CLOSE_BLOCKED: &str = "PERIOD_CLOSE_BLOCKED";
+const SUPPLIER_COMMITMENT_APPROVAL: &str = "SUPPLIER_COMMITMENT_APPROVAL";
 
 #[async_trait]
 pub trait AgentLoop {
     async fn tick(&self) -> Result<()>;
 }
 
-pub struct SalesAgent<TMessage, TInventory, TCommitment>
+pub struct RetryingAgent<A: AgentLoop> {
+    pub agent: A,
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+}
+
+impl<A: AgentLoop> RetryingAgent<A> {
+    pub fn new(agent: A, max_attempts: u32, base_delay: Duration) -> Self {
+        Self {
+            agent,
+            max_attempts: max_attempts.max(1),
+            base_delay,
+        }
+    }
+}
+
+#[async_trait]
+impl<A> AgentLoop for RetryingAgent<A>
+where
+    A: AgentLoop + Send + Sync,
+{
+    async fn tick(&self) -> Result<()> {
+        let max_attempts = self.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match self.agent.tick().await {
+                Ok(()) => return Ok(()),
+                Err(err) if attempt >= max_attempts => return Err(err),
+                Err(_) => {
+                    let backoff = self
+                        .base_delay
+                        .saturating_mul(2u32.saturating_pow(attempt - 1));
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+pub struct SalesAgent<TMessage, TInventory, TCommitment, TLeads, TCustomers>
 where
     TMessage: MessagingTool,
     TInventory: InventoryTool,
     TCommitment: CommitmentTool,
+    TLeads: LeadTool,
+    TCustomers: CustomerTool,
 {
     pub messaging: TMessage,
     pub inventory: TInventory,
     pub commitments: TCommitment,
+    pub leads: TLeads,
+    pub customers: TCustomers,
+    pub max_follow_ups_per_tick: usize,
 }
 
 #[async_trait]
-impl<TMessage, TInventory, TCommitment> AgentLoop for SalesAgent<TMessage, TInventory, TCommitment>
+impl<TMessage, TInventory, TCommitment, TLeads, TCustomers> AgentLoop
+    for SalesAgent<TMessage, TInventory, TCommitment, TLeads, TCustomers>
 where
     TMessage: MessagingTool + Send + Sync,
     TInventory: InventoryTool + Send + Sync,
     TCommitment: CommitmentTool + Send + Sync,
+    TLeads: LeadTool + Send + Sync,
+    TCustomers: CustomerTool + Send + Sync,
 {
     async fn tick(&self) -> Result<()> {
         let _ = self.inventory.quantity_available("SKU-001").await?;
+
+        let mut leads = self.leads.open_leads().await?;
+        leads.sort_by(|left, right| {
+            right
+                .score
+                .cmp(&left.score)
+                .then(left.created_at.cmp(&right.created_at))
+        });
+
+        for lead in leads.into_iter().take(self.max_follow_ups_per_tick) {
+            let customer = self
+                .customers
+                .customer_by_email(&lead.contact_email)
+                .await?;
+            if customer
+                .as_ref()
+                .and_then(|customer| customer.available_credit)
+                .is_some_and(|available| available <= Decimal::ZERO)
+            {
+                continue;
+            }
+
+            let body = json!({
+                "lead_id": lead.lead_id,
+                "status": lead.status,
+                "score": lead.score,
+                "customer_id": customer.as_ref().map(|customer| customer.customer_id),
+                "risk_class": customer.as_ref().map(|customer| customer.risk_class.as_str()),
+            });
+            self.messaging
+                .send_message(
+                    &lead.contact_email,
+                    &format!("Follow-up for lead {}", lead.lead_id),
+                    &body.to_string(),
+                )
+                .await?;
+            self.leads.record_follow_up(lead.lead_id).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReorderRule {
+    pub item_code: String,
+    pub reorder_point: f64,
+    pub reorder_quantity: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcurementConfig {
+    pub supplier_endpoint: String,
+    pub max_wait_seconds: u64,
+    pub auto_approve_threshold: Decimal,
+    pub currency: String,
+    pub max_unit_price: Option<Decimal>,
+    pub delivery_lead_days: i64,
+    pub max_counter_offers: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ShortagePayload {
+    item_code: String,
+    shortage_qty: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReplenishmentRequestedPayload {
+    shortage_event_id: Uuid,
+    request_id: Uuid,
+    item_code: String,
+    shortage_qty: f64,
+    supplier_endpoint: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReplenishmentNegotiationFailedPayload {
+    shortage_event_id: Uuid,
+    request_id: Uuid,
+    item_code: String,
+    reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReplenishmentCounterOfferedPayload {
+    shortage_event_id: Uuid,
+    request_id: Uuid,
+    counter_offers: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReplenishmentApprovalRequestedPayload {
+    shortage_event_id: Uuid,
+    request_id: Uuid,
+    escalation_id: Uuid,
+    commitment: SupplierCommitment,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReplenishmentRejectedPayload {
+    shortage_event_id: Uuid,
+    request_id: Uuid,
+    escalation_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReplenishmentCommittedPayload {
+    shortage_event_id: Uuid,
+    request_id: Uuid,
+    commitment_id: Uuid,
+    obligation_id: Option<Uuid>,
+    item_code: String,
+    quantity: f64,
+    total_amount: Decimal,
+    currency: String,
+    requires_approval: bool,
+}
+
+struct OutstandingShortage {
+    event_id: Uuid,
+    item_code: String,
+    shortage_qty: f64,
+    request_id: Option<Uuid>,
+    awaiting_reply_since: DateTime<Utc>,
+    counter_offers: u32,
+    pending_approval: Option<ReplenishmentApprovalRequestedPayload>,
+}
+
+pub struct ProcurementAgent<TMessage, TInventory, TCommitment, TEvents>
+where
+    TMessage: MessagingTool,
+    TInventory: InventoryTool,
+    TCommitment: CommitmentTool,
+    TEvents: EventStore,
+{
+    pub messaging: TMessage,
+    pub inventory: TInventory,
+    pub commitments: TCommitment,
+    pub events: TEvents,
+    pub shortage_stream: Uuid,
+    pub config: ProcurementConfig,
+    pub reorder_rules: Vec<ReorderRule>,
+    pub supplier_replies: Mutex<HashMap<Uuid, VecDeque<A2AMessage<ReplenishmentResponse>>>>,
+}
+
+impl<TMessage, TInventory, TCommitment, TEvents>
+    ProcurementAgent<TMessage, TInventory, TCommitment, TEvents>
+where
+    TMessage: MessagingTool,
+    TInventory: InventoryTool,
+    TCommitment: CommitmentTool,
+    TEvents: EventStore,
+{
+    async fn outstanding_shortages(&self) -> Result<Vec<OutstandingShortage>> {
+        let envelopes = self.events.stream(self.shortage_stream).await?;
+
+        let mut shortages = Vec::new();
+        let mut resolved = HashSet::new();
+        for envelope in envelopes {
+            let event = envelope.event;
+            let shortage_event_id = match event.kind {
+                DomainEventKind::InventoryShortage => {
+                    let payload: ShortagePayload = serde_json::from_value(event.payload)?;
+                    shortages.push(OutstandingShortage {
+                        event_id: event.id,
+                        item_code: payload.item_code,
+                        shortage_qty: payload.shortage_qty,
+                        request_id: None,
+                        awaiting_reply_since: event.occurred_at,
+                        counter_offers: 0,
+                        pending_approval: None,
+                    });
+                    continue;
+                }
+                DomainEventKind::ReplenishmentCommitted => {
+                    let payload: ReplenishmentCommittedPayload =
+                        serde_json::from_value(event.payload)?;
+                    resolved.insert(payload.shortage_event_id);
+                    continue;
+                }
+                DomainEventKind::ReplenishmentRejected => {
+                    let payload: ReplenishmentRejectedPayload =
+                        serde_json::from_value(event.payload)?;
+                    resolved.insert(payload.shortage_event_id);
+                    continue;
+                }
+                DomainEventKind::ReplenishmentRequested => {
+                    serde_json::from_value::<ReplenishmentRequestedPayload>(event.payload.clone())?
+                        .shortage_event_id
+                }
+                DomainEventKind::ReplenishmentCounterOffered => {
+                    serde_json::from_value::<ReplenishmentCounterOfferedPayload>(
+                        event.payload.clone(),
+                    )?
+                    .shortage_event_id
+                }
+                DomainEventKind::ReplenishmentApprovalRequested => {
+                    serde_json::from_value::<ReplenishmentApprovalRequestedPayload>(
+                        event.payload.clone(),
+                    )?
+                    .shortage_event_id
+                }
+                DomainEventKind::ReplenishmentNegotiationFailed => {
+                    serde_json::from_value::<ReplenishmentNegotiationFailedPayload>(
+                        event.payload.clone(),
+                    )?
+                    .shortage_event_id
+                }
+                _ => continue,
+            };
+
+            let Some(shortage) = shortages
+                .iter_mut()
+                .find(|shortage| shortage.event_id == shortage_event_id)
+            else {
+                continue;
+            };
+            match event.kind {
+                DomainEventKind::ReplenishmentRequested => {
+                    let payload: ReplenishmentRequestedPayload =
+                        serde_json::from_value(event.payload)?;
+                    shortage.request_id = Some(payload.request_id);
+                    shortage.awaiting_reply_since = event.occurred_at;
+                    shortage.counter_offers = 0;
+                }
+                DomainEventKind::ReplenishmentCounterOffered => {
+                    let payload: ReplenishmentCounterOfferedPayload =
+                        serde_json::from_value(event.payload)?;
+                    shortage.awaiting_reply_since = event.occurred_at;
+                    shortage.counter_offers = payload.counter_offers;
+                }
+                DomainEventKind::ReplenishmentApprovalRequested => {
+                    shortage.pending_approval = Some(serde_json::from_value(event.payload)?);
+                }
+                DomainEventKind::ReplenishmentNegotiationFailed => {
+                    shortage.request_id = None;
+                    shortage.counter_offers = 0;
+                }
+                _ => {}
+            }
+        }
+
+        shortages.retain(|shortage| !resolved.contains(&shortage.event_id));
+        Ok(shortages)
+    }
+
+    async fn record_reorder_shortages(&self, outstanding: &[OutstandingShortage]) -> Result<()> {
+        for rule in &self.reorder_rules {
+            if outstanding
+                .iter()
+                .any(|shortage| shortage.item_code == rule.item_code)
+            {
+                continue;
+            }
+
+            let available = self.inventory.quantity_available(&rule.item_code).await?;
+            if available >= rule.reorder_point {
+                continue;
+            }
+
+            let shortage_qty = rule.reorder_quantity.max(rule.reorder_point - available);
+            self.append_event(
+                DomainEventKind::InventoryShortage,
+                &ShortagePayload {
+                    item_code: rule.item_code.clone(),
+                    shortage_qty,
+                },
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends the request for a shortage. The supplier request is keyed on the shortage and the
+    /// A2A message on the request, so a retry after a failed append re-sends the same request.
+    async fn request_replenishment(&self, shortage: &mut OutstandingShortage) -> Result<Uuid> {
+        let request_id = self
+            .commitments
+            .create_supplier_commitment(
+                shortage.event_id,
+                &self.config.supplier_endpoint,
+                &shortage.item_code,
+                shortage.shortage_qty,
+            )
+            .await?;
+
+        let mut message = A2AMessage::new(
+            PROCUREMENT_AGENT_ID,
+            &self.config.supplier_endpoint,
+            &format!("replenishment:{request_id}"),
+            self.replenishment_terms(shortage)?,
+        );
+        message.correlation_id = request_id;
+        self.messaging.send_a2a_message(&message).await?;
+
+        self.append_event(
+            DomainEventKind::ReplenishmentRequested,
+            &ReplenishmentRequestedPayload {
+                shortage_event_id: shortage.event_id,
+                request_id,
+                item_code: shortage.item_code.clone(),
+                shortage_qty: shortage.shortage_qty,
+                supplier_endpoint: self.config.supplier_endpoint.clone(),
+            },
+        )
+        .await?;
+
+        shortage.request_id = Some(request_id);
+        shortage.awaiting_reply_since = Utc::now();
+        shortage.counter_offers = 0;
+        Ok(request_id)
+    }
+
+    fn replenishment_terms(&self, shortage: &OutstandingShortage) -> Result<ReplenishmentRequest> {
+        Ok(ReplenishmentRequest {
+            item_code: shortage.item_code.clone(),
+            shortage_qty: Decimal::try_from(shortage.shortage_qty)?,
+            currency: self.config.currency.clone(),
+            requested_delivery_by: Utc::now()
+                + chrono::Duration::days(self.config.delivery_lead_days),
+            max_unit_price: self.config.max_unit_price,
+        })
+    }
+
+    /// Moves the negotiation on by at most one supplier reply. Returns `None` while the
+    /// supplier has not answered; an unanswered request is failed after `max_wait_seconds`.
+    async fn negotiate_commitment(
+        &self,
+        shortage: &OutstandingShortage,
+        request_id: Uuid,
+    ) -> Result<Option<SupplierCommitment>> {
+        let Some(reply) = self.take_supplier_reply(request_id) else {
+            let waited = Utc::now() - shortage.awaiting_reply_since;
+            if waited.num_seconds() >= i64::try_from(self.config.max_wait_seconds)? {
+                let reason = format!(
+                    "no supplier reply within {} seconds",
+                    self.config.max_wait_seconds
+                );
+                self.record_negotiation_failure(shortage, request_id, &reason)
+                    .await?;
+            }
+            return Ok(None);
+        };
+
+        let offer = match &reply.payload {
+            ReplenishmentResponse::Commitment(offer) => offer,
+            ReplenishmentResponse::NegotiationFailed(failed) => {
+                self.record_negotiation_failure(shortage, request_id, &failed.reason)
+                    .await?;
+                return Ok(None);
+            }
+        };
+
+        let terms = self.replenishment_terms(shortage)?;
+        match terms.evaluate(offer) {
+            Ok(()) => Ok(Some(SupplierCommitment {
+                request_id,
+                commitment_id: reply.message_id,
+                supplier: offer.supplier_ref.clone(),
+                item_code: offer.item_code.clone(),
+                quantity: offer
+                    .committed_qty
+                    .to_f64()
+                    .unwrap_or(shortage.shortage_qty),
+                total_amount: (offer.committed_qty * offer.unit_price).round_dp(4),
+                currency: offer.currency.clone(),
+            })),
+            Err(_) if shortage.counter_offers < self.config.max_counter_offers => {
+                let counter_offers = shortage.counter_offers + 1;
+                let counter = reply.reply(
+                    &format!("replenishment:{request_id}:counter:{counter_offers}"),
+                    ReplenishmentRequest {
+                        requested_delivery_by: offer
+                            .estimated_delivery_at
+                            .max(terms.requested_delivery_by),
+                        ..terms.clone()
+                    },
+                );
+                self.messaging.send_a2a_message(&counter).await?;
+                self.append_event(
+                    DomainEventKind::ReplenishmentCounterOffered,
+                    &ReplenishmentCounterOfferedPayload {
+                        shortage_event_id: shortage.event_id,
+                        request_id,
+                        counter_offers,
+                    },
+                )
+                .await?;
+                Ok(None)
+            }
+            Err(reason) => {
+                let failure = reply.reply(
+                    &format!("replenishment:{request_id}:failed"),
+                    ReplenishmentResponse::NegotiationFailed(ReplenishmentNegotiationFailed {
+                        item_code: shortage.item_code.clone(),
+                        reason: reason.clone(),
+                    }),
+                );
+                self.messaging.send_a2a_message(&failure).await?;
+                self.record_negotiation_failure(shortage, request_id, &reason)
+                    .await?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Moves every reply waiting in the inbox into `supplier_replies` without blocking.
+    async fn collect_supplier_replies(&self) -> Result<()> {
+        let received = self
+            .messaging
+            .receive_a2a_messages::<ReplenishmentResponse>(PROCUREMENT_AGENT_ID)
+            .await?;
+        let mut replies = self
+            .supplier_replies
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        for message in received {
+            replies
+                .entry(message.correlation_id)
+                .or_default()
+                .push_back(message);
+        }
+
+        Ok(())
+    }
+
+    fn take_supplier_reply(&self, request_id: Uuid) -> Option<A2AMessage<ReplenishmentResponse>> {
+        self.supplier_replies
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get_mut(&request_id)
+            .and_then(|queue| queue.pop_front())
+    }
+
+    async fn record_negotiation_failure(
+        &self,
+        shortage: &OutstandingShortage,
+        request_id: Uuid,
+        reason: &str,
+    ) -> Result<()> {
+        self.append_event(
+            DomainEventKind::ReplenishmentNegotiationFailed,
+            &ReplenishmentNegotiationFailedPayload {
+                shortage_event_id: shortage.event_id,
+                request_id,
+                item_code: shortage.item_code.clone(),
+                reason: reason.to_string(),
+            },
+        )
+        .await
+    }
+
+    async fn request_approval(
+        &self,
+        shortage: &OutstandingShortage,
+        commitment: SupplierCommitment,
+    ) -> Result<()> {
+        let escalation_id = self
+            .commitments
+            .create_escalation(
+                SUPPLIER_COMMITMENT_APPROVAL,
+                &json!({
+                    "shortage_event_id": shortage.event_id,
+                    "auto_approve_threshold": self.config.auto_approve_threshold,
+                    "commitment": commitment,
+                }),
+            )
+            .await?;
+
+        self.append_event(
+            DomainEventKind::ReplenishmentApprovalRequested,
+            &ReplenishmentApprovalRequestedPayload {
+                shortage_event_id: shortage.event_id,
+                request_id: commitment.request_id,
+                escalation_id,
+                commitment,
+            },
+        )
+        .await
+    }
+
+    async fn settle_approval(
+        &self,
+        shortage: &OutstandingShortage,
+        approval: &ReplenishmentApprovalRequestedPayload,
+        dispatched: &mut HashMap<Uuid, Uuid>,
+    ) -> Result<()> {
+        let status = self
+            .commitments
+            .escalation_status(approval.escalation_id)
+            .await?;
+        match status.as_str() {
+            "APPROVED" => {
+                self.commit(shortage, approval.commitment.clone(), true, dispatched)
+                    .await
+            }
+            "REJECTED" => {
+                self.append_event(
+                    DomainEventKind::ReplenishmentRejected,
+                    &ReplenishmentRejectedPayload {
+                        shortage_event_id: shortage.event_id,
+                        request_id: approval.request_id,
+                        escalation_id: approval.escalation_id,
+                    },
+                )
+                .await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn commit(
+        &self,
+        shortage: &OutstandingShortage,
+        commitment: SupplierCommitment,
+        requires_approval: bool,
+        dispatched: &mut HashMap<Uuid, Uuid>,
+    ) -> Result<()> {
+        let obligation_id = match dispatched.get(&commitment.commitment_id) {
+            Some(obligation_id) => *obligation_id,
+            None => {
+                let obligation_id = self
+                    .commitments
+                    .create_procurement_obligation(&commitment)
+                    .await?;
+                dispatched.insert(commitment.commitment_id, obligation_id);
+                obligation_id
+            }
+        };
+
+        self.append_event(
+            DomainEventKind::ReplenishmentCommitted,
+            &ReplenishmentCommittedPayload {
+                shortage_event_id: shortage.event_id,
+                request_id: commitment.request_id,
+                commitment_id: commitment.commitment_id,
+                obligation_id: Some(obligation_id),
+                item_code: commitment.item_code,
+                quantity: commitment.quantity,
+                total_amount: commitment.total_amount,
+                currency: commitment.currency,
+                requires_approval,
+            },
+        )
+        .await
+    }
+
+    async fn append_event<T: Serialize>(&self, kind: DomainEventKind, payload: &T) -> Result<()> {
+        self.events
+            .append(
+                self.shortage_stream,
+                DomainEvent {
+                    id: Uuid::new_v4(),
+                    aggregate_id: self.shortage_stream,
+                    kind,
+                    occurred_at: Utc::now(),
+                    payload: serde_json::to_value(payload)?,
+                },
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<TMessage, TInventory, TCommitment, TEvents> AgentLoop
+    for ProcurementAgent<TMessage, TInventory, TCommitment, TEvents>
+where
+    TMessage: MessagingTool + Send + Sync,
+    TInventory: InventoryTool + Send + Sync,
+    TCommitment: CommitmentTool + Send + Sync,
+    TEvents: EventStore + Send + Sync,
+{
+    async fn tick(&self) -> Result<()> {
+        let outstanding = self.outstanding_shortages().await?;
+        self.record_reorder_shortages(&outstanding).await?;
+        self.collect_supplier_replies().await?;
+
+        let mut dispatched = HashMap::new();
+        for mut shortage in self.outstanding_shortages().await? {
+            if let Some(approval) = &shortage.pending_approval {
+                self.settle_approval(&shortage, approval, &mut dispatched)
+                    .await?;
+                continue;
+            }
+
+            let request_id = match shortage.request_id {
+                Some(request_id) => request_id,
+                None => self.request_replenishment(&mut shortage).await?,
+            };
+
+            let Some(commitment) = self.negotiate_commitment(&shortage, request_id).await? else {
+                continue;
+            };
+
+            if commitment.total_amount > self.config.auto_approve_threshold {
+                self.request_approval(&shortage, commitment).await?;
+            } else {
+                self.commit(&shortage, commitment, false, &mut dispatched)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WarehouseConfig {
+    pub procurement_recipient: String,
+    pub max_reservation_attempts: u32,
+    pub reservation_backoff: Duration,
+    /// How long a tick waits for the first `orders.created` message before moving on.
+    pub order_wait: Duration,
+    /// Stream holding the sequence through which shortages have been forwarded to procurement.
+    pub forwarding_checkpoint_stream: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ShortagesForwardedPayload {
+    through_sequence: i64,
+}
+
+pub type OrderCreatedStream = Pin<Box<dyn Stream<Item = Result<OrderCreatedEvent>> + Send>>;
+
+pub fn order_created_events(redis: &RedisBus) -> OrderCreatedStream {
+    Box::pin(redis.subscribe_json::<OrderCreatedEvent>(ORDERS_CREATED_CHANNEL))
+}
+
+pub struct WarehouseAgent<TInventory, TMessage, TOrders, TEvents>
+where
+    TInventory: InventoryTool,
+    TMessage: MessagingTool,
+    TOrders: OrderTool,
+    TEvents: EventStore,
+{
+    pub inventory: TInventory,
+    pub messaging: TMessage,
+    pub orders: TOrders,
+    pub events: TEvents,
+    pub shortage_stream: Uuid,
+    pub config: WarehouseConfig,
+    orders_created: tokio::sync::Mutex<OrderCreatedStream>,
+}
+
+impl<TInventory, TMessage, TOrders, TEvents> WarehouseAgent<TInventory, TMessage, TOrders, TEvents>
+where
+    TInventory: InventoryTool,
+    TMessage: MessagingTool,
+    TOrders: OrderTool,
+    TEvents: EventStore,
+{
+    pub fn new(
+        inventory: TInventory,
+        messaging: TMessage,
+        orders: TOrders,
+        events: TEvents,
+        orders_created: OrderCreatedStream,
+        shortage_stream: Uuid,
+        config: WarehouseConfig,
+    ) -> Self {
+        Self {
+            inventory,
+            messaging,
+            orders,
+            events,
+            shortage_stream,
+            config,
+            orders_created: tokio::sync::Mutex::new(orders_created),
+        }
+    }
+
+    async fn created_orders(&self) -> Vec<Uuid> {
+        let mut orders_created = self.orders_created.lock().await;
+        let mut next = tokio::time::timeout(self.config.order_wait, orders_created.next())
+            .await
+            .ok()
+            .flatten();
+
+        let mut order_ids = Vec::new();
+        while let Some(event) = next {
+            match event {
+                Ok(event) => order_ids.push(event.order_id),
+                Err(err) => warn!("skipping unreadable {ORDERS_CREATED_CHANNEL} message: {err:#}"),
+            }
+            next = orders_created.next().now_or_never().flatten();
+        }
+        order_ids
+    }
+
+    async fn reserve_with_backoff(&self, line: &OrderLine) -> Result<Reservation> {
+        let max_attempts = self.config.max_reservation_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match self
+                .inventory
+                .reserve_quantity(&line.item_code, line.quantity)
+                .await
+            {
+                Ok(reservation) => return Ok(reservation),
+                Err(err) if attempt >= max_attempts => return Err(err),
+                Err(_) => {
+                    let backoff = self
+                        .config
+                        .reservation_backoff
+                        .saturating_mul(2u32.saturating_pow(attempt - 1));
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    async fn handle_order(&self, order_id: Uuid) -> Result<()> {
+        let line = self.orders.order_line(order_id).await?;
+
+        match self.reserve_with_backoff(&line).await? {
+            Reservation::Reserved { .. } => self.orders.fulfill_order(order_id).await,
+            Reservation::InsufficientStock { available } => {
+                self.orders
+                    .mark_awaiting_stock(&OutOfStockEvent {
+                        order_id,
+                        item_code: line.item_code.clone(),
+                        requested_quantity: line.quantity,
+                        available_quantity: available,
+                    })
+                    .await?;
+
+                self.events
+                    .append(
+                        self.shortage_stream,
+                        DomainEvent {
+                            id: Uuid::new_v4(),
+                            aggregate_id: self.shortage_stream,
+                            kind: DomainEventKind::InventoryShortage,
+                            occurred_at: Utc::now(),
+                            payload: serde_json::to_value(ShortagePayload {
+                                item_code: line.item_code,
+                                shortage_qty: line.quantity - available,
+                            })?,
+                        },
+                    )
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+
+    async fn forwarded_through(&self) -> Result<i64> {
+        let checkpoints = self
+            .events
+            .stream(self.config.forwarding_checkpoint_stream)
+            .await?;
+        let Some(checkpoint) = checkpoints.into_iter().last() else {
+            return Ok(0);
+        };
+        let payload: ShortagesForwardedPayload = serde_json::from_value(checkpoint.event.payload)?;
+        Ok(payload.through_sequence)
+    }
+
+    async fn forward_shortage(&self, event: DomainEvent) -> Result<()> {
+        let payload: ShortagePayload = serde_json::from_value(event.payload)?;
+        let body = json!({
+            "shortage_event_id": event.id,
+            "item_code": payload.item_code,
+            "shortage_qty": payload.shortage_qty,
+            "requested_by": WAREHOUSE_AGENT_ID,
+        });
+        self.messaging
+            .send_message(
+                &self.config.procurement_recipient,
+                &format!("Inventory shortage {}", payload.item_code),
+                &body.to_string(),
+            )
+            .await
+    }
+
+    async fn forward_shortages(&self) -> Result<()> {
+        let checkpoint = self.forwarded_through().await?;
+        let envelopes = self
+            .events
+            .stream_after(self.shortage_stream, checkpoint)
+            .await?;
+
+        let mut forwarded_through = checkpoint;
+        let mut forwarded = Ok(());
+        for envelope in envelopes {
+            if let DomainEventKind::InventoryShortage = envelope.event.kind
+                && let Err(err) = self.forward_shortage(envelope.event).await
+            {
+                forwarded = Err(err);
+                break;
+            }
+            forwarded_through = envelope.sequence;
+        }
+
+        if forwarded_through > checkpoint {
+            let stream_id = self.config.forwarding_checkpoint_stream;
+            self.events
+                .append(
+                    stream_id,
+                    DomainEvent {
+                        id: Uuid::new_v4(),
+                        aggregate_id: stream_id,
+                        kind: DomainEventKind::InventoryShortagesForwarded,
+                        occurred_at: Utc::now(),
+                        payload: serde_json::to_value(ShortagesForwardedPayload {
+                            through_sequence: forwarded_through,
+                        })?,
+                    },
+                )
+                .await?;
+        }
+
+        forwarded
+    }
+}
+
+#[async_trait]
+impl<TInventory, TMessage, TOrders, TEvents> AgentLoop
+    for WarehouseAgent<TInventory, TMessage, TOrders, TEvents>
+where
+    TInventory: InventoryTool + Send + Sync,
+    TMessage: MessagingTool + Send + Sync,
+    TOrders: OrderTool + Send + Sync,
+    TEvents: EventStore + Send + Sync,
+{
+    async fn tick(&self) -> Result<()> {
+        for order_id in self.created_orders().await {
+            if let Err(err) = self.handle_order(order_id).await {
+                warn!("warehouse agent could not handle order {order_id}: {err:#}");
+            }
+        }
+
+        self.forward_shortages().await
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArAgentConfig {
+    pub reminder_days: i64,
+    pub escalation_days: i64,
+}
+
+pub struct ArAgent<TMessage, TReceivables, TEvents>
+where
+    TMessage: MessagingTool,
+    TReceivables: ReceivablesTool,
+    TEvents: EventStore,
+{
+    pub messaging: TMessage,
+    pub receivables: TReceivables,
+    pub events: TEvents,
+    pub stream_id: Uuid,
+    pub config: ArAgentConfig,
+}
+
+impl<TMessage, TReceivables, TEvents> ArAgent<TMessage, TReceivables, TEvents>
+where
+    TMessage: MessagingTool,
+    TReceivables: ReceivablesTool,
+    TEvents: EventStore,
+{
+    fn collection_stage(&self, age_days: i64) -> Option<CollectionStage> {
+        if age_days > self.config.escalation_days {
+            Some(CollectionStage::Escalated)
+        } else if age_days > self.config.reminder_days {
+            Some(CollectionStage::Reminded)
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl<TMessage, TReceivables, TEvents> AgentLoop for ArAgent<TMessage, TReceivables, TEvents>
+where
+    TMessage: MessagingTool + Send + Sync,
+    TReceivables: ReceivablesTool + Send + Sync,
+    TEvents: EventStore + Send + Sync,
+{
+    async fn tick(&self) -> Result<()> {
+        let mut invoices_raised = Vec::new();
+        for order_id in self.receivables.uninvoiced_fulfilled_orders().await? {
+            invoices_raised.push(self.receivables.generate_invoice(order_id).await?);
+        }
+
+        let mut reminders_sent = 0;
+        let mut escalations_created = Vec::new();
+        for item in self.receivables.aging_items().await? {
+            let Some(stage) = self.collection_stage(item.age_days) else {
+                continue;
+            };
+            if item.collection_stage >= Some(stage) {
+                continue;
+            }
+
+            if item.collection_stage.is_none() {
+                self.messaging
+                    .send_message(
+                        &item.customer_email,
+                        &format!("Payment reminder for invoice {}", item.invoice_id),
+                        &format!(
+                            "Invoice {} for order {} has {} {} outstanding and is {} days past issue.",
+                            item.invoice_id,
+                            item.order_id,
+                            item.outstanding,
+                            item.currency,
+                            item.age_days
+                        ),
+                    )
+                    .await?;
+                reminders_sent += 1;
+            }
+
+            if stage == CollectionStage::Escalated {
+                escalations_created.push(
+                    self.receivables
+                        .escalate_collection(&item, AR_COLLECTION_BREACH)
+                        .await?,
+                );
+            }
+
+            self.receivables
+                .record_collection_stage(item.invoice_id, stage)
+                .await?;
+        }
+
+        self.events
+            .append(
+                self.stream_id,
+                DomainEvent {
+                    id: Uuid::new_v4(),
+                    aggregate_id: self.stream_id,
+                    kind: DomainEventKind::ArAgentTickCompleted,
+                    occurred_at: Utc::now(),
+                    payload: json!({
+                        "invoices_raised": invoices_raised,
+                        "reminders_sent": reminders_sent,
+                        "escalations_created": escalations_created,
+                    }),
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ControllerConfig {
+    pub close_day_of_month: u32,
+    pub tolerance_pct: Decimal,
+}
+
+pub struct ControllerAgent<TClose>
+where
+    TClose: PeriodCloseTool,
+{
+    pub close: TClose,
+    pub config: ControllerConfig,
+}
+
+impl<TClose> ControllerAgent<TClose>
+where
+    TClose: PeriodCloseTool,
+{
+    pub fn new(close: TClose, config: ControllerConfig) -> Self {
+        Self { close, config }
+    }
+
+    async fn closing_period(&self, today: NaiveDate) -> Result<Option<(NaiveDate, NaiveDate)>> {
+        if today.day() < self.config.close_day_of_month {
+            return Ok(None);
+        }
+
+        let period_end = today
+            .with_day(1)
+            .and_then(|month_start| month_start.pred_opt())
+            .context("failed to derive closing period end")?;
+        let period_start = period_end
+            .with_day(1)
+            .context("failed to derive closing period start")?;
+
+        if self.close.period_closed(period_start, period_end).await? {
+            return Ok(None);
+        }
+
+        Ok(Some((period_start, period_end)))
+    }
+
+    async fn check_trial_balance(&self, period_end: NaiveDate) -> Result<(bool, String)> {
+        let totals = self.close.trial_balance(period_end).await?;
+        let difference = (totals.total_debits - totals.total_credits).abs();
+        let tolerance = totals.total_debits.abs().max(totals.total_credits.abs())
+            * self.config.tolerance_pct
+            / Decimal::from(100);
+
+        Ok((
+            difference <= tolerance,
+            format!(
+                "debits {} credits {} difference {} tolerance {}",
+                totals.total_debits,
+                totals.total_credits,
+                difference,
+                tolerance.round_dp(4)
+            ),
+        ))
+    }
+
+    async fn run_allocation(
+        &self,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> Result<(bool, String)> {
+        self.close.run_allocation(period_start, period_end).await?;
+        Ok((true, "allocation completed".to_string()))
+    }
+
+    async fn check_reconciliation(
+        &self,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> Result<(bool, String)> {
+        let status = self
+            .close
+            .reconciliation_status(period_start, period_end)
+            .await?;
+        Ok((
+            status == "BALANCED",
+            format!("reconciliation status {status}"),
+        ))
+    }
+
+    async fn evaluate_kpi_variances(
+        &self,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> Result<(bool, String)> {
+        let targets = self
+            .close
+            .active_kpi_targets(period_start, period_end)
+            .await?;
+
+        let mut breaches = 0;
+        let mut failures = Vec::new();
+        for target in &targets {
+            match self
+                .close
+                .evaluate_variance(period_start, period_end, target)
+                .await
+            {
+                Ok(status) if status == "BREACH" => breaches += 1,
+                Ok(_) => {}
+                Err(err) => failures.push(format!(
+                    "{}/{}/{}: {err}",
+                    target.business_unit, target.mandate, target.metric_name
+                )),
+            }
+        }
+
+        let mut detail = format!("evaluated {} targets, {} breached", targets.len(), breaches);
+        if !failures.is_empty() {
+            detail = format!("{detail}; failed: {}", failures.join("; "));
+        }
+
+        Ok((failures.is_empty(), detail))
+    }
+
+    async fn post_recurring_journals(
+        &self,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> Result<(bool, String)> {
+        let posted = self
+            .close
+            .post_due_recurring_journals(period_start, period_end)
+            .await?;
+        Ok((true, format!("posted {posted} recurring journals")))
+    }
+}
+
+fn step_outcome(step: &str, result: Result<(bool, String)>) -> PeriodCloseStepOutcome {
+    let (passed, detail) = result.unwrap_or_else(|err| (false, err.to_string()));
+    PeriodCloseStepOutcome {
+        step: step.to_string(),
+        passed,
+        detail,
+    }
+}
+
+#[async_trait]
+impl<TClose> AgentLoop for ControllerAgent<TClose>
+where
+    TClose: PeriodCloseTool + Send + Sync,
+{
+    async fn tick(&self) -> Result<()> {
+        let Some((period_start, period_end)) = self.closing_period(Utc::now().date_naive()).await?
+        else {
+            return Ok(());
+        };
+
+        let steps = vec![
+            step_outcome("TRIAL_BALANCE", self.check_trial_balance(period_end).await),
+            step_outcome(
+                "FINOPS_ALLOCATION",
+                self.run_allocation(period_start, period_end).await,
+            ),
+            step_outcome(
+                "RECONCILIATION",
+                self.check_reconciliation(period_start, period_end).await,
+            ),
+            step_outcome(
+                "KPI_VARIANCE",
+                self.evaluate_kpi_variances(period_start, period_end).await,
+            ),
+            step_outcome(
+                "RECURRING_JOURNALS",
+                self.post_recurring_journals(period_start, period_end).await,
+            ),
+        ];
+
+        let failed_steps = steps
+            .iter()
+            .filter(|outcome| !outcome.passed)
+            .collect::<Vec<_>>();
+        let escalation_id = if failed_steps.is_empty() {
+            None
+        } else {
+            Some(
+                self.close
+                    .create_escalation(
+                        PERIOD_CLOSE_BLOCKED,
+                        &json!({
+                            "period_start": period_start,
+                            "period_end": period_end,
+                            "failed_steps": failed_steps,
+                        }),
+                    )
+                    .await?,
+            )
+        };
+
+        self.close
+            .record_period_close(&PeriodCloseLog {
+                period_start,
+                period_end,
+                steps,
+                escalation_id,
+            })
+            .await?;
+
+        Ok(())
+    }
+}
+
+pub fn evidence_hash(evidence: &Value) -> Result<String> {
+    let bytes = serde_json::to_vec(evidence)?;
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+pub struct AuditAgent<TAudit, TEvents>
+where
+    TAudit: AuditTool,
+    TEvents: EventStore,
+{
+    pub audit: TAudit,
+    pub events: TEvents,
+}
+
+#[async_trait]
+impl<TAudit, TEvents> AgentLoop for AuditAgent<TAudit, TEvents>
+where
+    TAudit: AuditTool + Send + Sync,
+    TEvents: EventStore + Send + Sync,
+{
+    async fn tick(&self) -> Result<()> {
+        for request in self.audit.pending_audit_requests().await? {
+            let evidence = self.audit.order_evidence(request.order_id).await?;
+            let hash = evidence_hash(&evidence)?;
+            let evidence_id = self
+                .audit
+                .store_evidence(&request, &hash, &evidence)
+                .await?;
+
+            self.events
+                .append(
+                    request.order_id,
+                    DomainEvent {
+                        id: Uuid::new_v4(),
+                        aggregate_id: request.order_id,
+                        kind: DomainEventKind::AuditEvidenceGenerated {
+                            order_id: request.order_id,
+                            evidence_hash: hash,
+                        },
+                        occurred_at: Utc::now(),
+                        payload: json!({
+                            "audit_request_id": request.request_id,
+                            "evidence_id": evidence_id,
+                        }),
+                    },
+                )
+                .await?;
+
+            self.audit
+                .complete_audit_request(request.request_id)
+                .await?;
+        }
+
         Ok(())
     }
 }
@@ -39,3 +1329,1272 @@ impl AgentLoop for BoardAgent {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::Instant;
+    use zavora_core::SupplierCommitment as SupplierOffer;
+    use zavora_eventstore::InMemoryEventStore;
+    use zavora_tools::{ArAgingItem, AuditRequest, KpiTargetKey, TrialBalanceTotals};
+
+    const SUPPLIER: &str = "supplier-agent";
+
+    #[derive(Default)]
+    struct FakeMessaging {
+        sent: Mutex<Vec<String>>,
+        inbox: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl MessagingTool for FakeMessaging {
+        async fn send_message(&self, recipient: &str, _subject: &str, _body: &str) -> Result<()> {
+            self.sent.lock().unwrap().push(recipient.to_string());
+            Ok(())
+        }
+
+        async fn pending_a2a_messages(&self, _recipient_agent_id: &str) -> Result<Vec<String>> {
+            Ok(std::mem::take(&mut *self.inbox.lock().unwrap()))
+        }
+    }
+
+    struct FakeInventory {
+        available: f64,
+    }
+
+    #[async_trait]
+    impl InventoryTool for FakeInventory {
+        async fn quantity_available(&self, _item_code: &str) -> Result<f64> {
+            Ok(self.available)
+        }
+
+        async fn reserve_quantity(&self, _item_code: &str, _quantity: f64) -> Result<Reservation> {
+            Ok(Reservation::InsufficientStock {
+                available: self.available,
+            })
+        }
+    }
+
+    struct FakeCommitments {
+        request_id: Uuid,
+        supplier_requests: Mutex<Vec<(String, String, f64)>>,
+        obligations: Mutex<Vec<Uuid>>,
+        escalations: Mutex<Vec<(String, Value)>>,
+        escalation_status: Mutex<&'static str>,
+    }
+
+    impl FakeCommitments {
+        fn new(request_id: Uuid) -> Self {
+            Self {
+                request_id,
+                supplier_requests: Mutex::default(),
+                obligations: Mutex::default(),
+                escalations: Mutex::default(),
+                escalation_status: Mutex::new("PENDING"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CommitmentTool for FakeCommitments {
+        async fn create_sales_commitment(&self, _customer: &str, _quote: &str) -> Result<Uuid> {
+            Ok(Uuid::new_v4())
+        }
+
+        async fn create_supplier_commitment(
+            &self,
+            _shortage_id: Uuid,
+            supplier: &str,
+            item_code: &str,
+            quantity: f64,
+        ) -> Result<Uuid> {
+            self.supplier_requests.lock().unwrap().push((
+                supplier.to_string(),
+                item_code.to_string(),
+                quantity,
+            ));
+            Ok(self.request_id)
+        }
+
+        async fn create_procurement_obligation(
+            &self,
+            commitment: &SupplierCommitment,
+        ) -> Result<Uuid> {
+            self.obligations
+                .lock()
+                .unwrap()
+                .push(commitment.commitment_id);
+            Ok(Uuid::new_v4())
+        }
+
+        async fn create_escalation(&self, action_type: &str, details: &Value) -> Result<Uuid> {
+            self.escalations
+                .lock()
+                .unwrap()
+                .push((action_type.to_string(), details.clone()));
+            Ok(Uuid::new_v4())
+        }
+
+        async fn escalation_status(&self, _escalation_id: Uuid) -> Result<String> {
+            Ok(self.escalation_status.lock().unwrap().to_string())
+        }
+    }
+
+    fn procurement_agent(
+        available: f64,
+        reorder_rules: Vec<ReorderRule>,
+        request_id: Uuid,
+    ) -> ProcurementAgent<FakeMessaging, FakeInventory, FakeCommitments, InMemoryEventStore> {
+        ProcurementAgent {
+            messaging: FakeMessaging::default(),
+            inventory: FakeInventory { available },
+            commitments: FakeCommitments::new(request_id),
+            events: InMemoryEventStore::default(),
+            shortage_stream: Uuid::new_v4(),
+            config: ProcurementConfig {
+                supplier_endpoint: SUPPLIER.to_string(),
+                max_wait_seconds: 0,
+                auto_approve_threshold: Decimal::new(10_000, 0),
+                currency: "USD".to_string(),
+                max_unit_price: None,
+                delivery_lead_days: 7,
+                max_counter_offers: 0,
+            },
+            reorder_rules,
+            supplier_replies: Mutex::default(),
+        }
+    }
+
+    fn supplier_reply(request_id: Uuid, item_code: &str, quantity: i64) -> String {
+        supplier_offer(request_id, item_code, quantity, 25)
+    }
+
+    fn supplier_offer(request_id: Uuid, item_code: &str, quantity: i64, unit_price: i64) -> String {
+        let mut reply = A2AMessage::new(
+            SUPPLIER,
+            PROCUREMENT_AGENT_ID,
+            &format!("replenishment:{request_id}:commitment"),
+            ReplenishmentResponse::Commitment(SupplierOffer {
+                item_code: item_code.to_string(),
+                committed_qty: Decimal::new(quantity, 0),
+                unit_price: Decimal::new(unit_price, 0),
+                currency: "USD".to_string(),
+                estimated_delivery_at: Utc::now() + chrono::Duration::days(30),
+                supplier_ref: "ACME-SUPPLY".to_string(),
+                commitment_expires_at: Utc::now() + chrono::Duration::days(2),
+            }),
+        );
+        reply.correlation_id = request_id;
+        serde_json::to_string(&reply).unwrap()
+    }
+
+    struct FlakyAgent {
+        failures_left: Mutex<u32>,
+        calls: Mutex<u32>,
+    }
+
+    impl FlakyAgent {
+        fn failing(times: u32) -> Self {
+            Self {
+                failures_left: Mutex::new(times),
+                calls: Mutex::default(),
+            }
+        }
+
+        fn calls(&self) -> u32 {
+            *self.calls.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl AgentLoop for FlakyAgent {
+        async fn tick(&self) -> Result<()> {
+            let call = {
+                let mut calls = self.calls.lock().unwrap();
+                *calls += 1;
+                *calls
+            };
+            let mut failures_left = self.failures_left.lock().unwrap();
+            if *failures_left > 0 {
+                *failures_left -= 1;
+                anyhow::bail!("transient failure on call {call}");
+            }
+            Ok(())
+        }
+    }
+
+    async fn events_of_kind(
+        agent: &ProcurementAgent<FakeMessaging, FakeInventory, FakeCommitments, InMemoryEventStore>,
+        kind: DomainEventKind,
+    ) -> Vec<Value> {
+        agent
+            .events
+            .stream(agent.shortage_stream)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|envelope| {
+                std::mem::discriminant(&envelope.event.kind) == std::mem::discriminant(&kind)
+            })
+            .map(|envelope| envelope.event.payload)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn retrying_agent_succeeds_after_transient_failures() {
+        let agent = RetryingAgent::new(FlakyAgent::failing(2), 3, Duration::from_millis(2));
+        let started = Instant::now();
+
+        agent.tick().await.unwrap();
+
+        assert_eq!(agent.agent.calls(), 3);
+        assert!(started.elapsed() >= Duration::from_millis(2 + 4));
+    }
+
+    #[tokio::test]
+    async fn retrying_agent_gives_up_with_the_last_error() {
+        let agent = RetryingAgent::new(FlakyAgent::failing(5), 3, Duration::from_millis(1));
+
+        let err = agent.tick().await.unwrap_err();
+
+        assert_eq!(agent.agent.calls(), 3);
+        assert_eq!(err.to_string(), "transient failure on call 3");
+    }
+
+    #[tokio::test]
+    async fn retrying_agent_always_makes_at_least_one_attempt() {
+        let agent = RetryingAgent::new(FlakyAgent::failing(1), 0, Duration::from_millis(1));
+
+        assert!(agent.tick().await.is_err());
+        assert_eq!(agent.agent.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn procurement_tick_turns_a_shortage_into_a_supplier_commitment() {
+        let request_id = Uuid::new_v4();
+        let agent = procurement_agent(
+            2.0,
+            vec![ReorderRule {
+                item_code: "SKU-001".to_string(),
+                reorder_point: 10.0,
+                reorder_quantity: 20.0,
+            }],
+            request_id,
+        );
+        agent
+            .messaging
+            .inbox
+            .lock()
+            .unwrap()
+            .push(supplier_reply(request_id, "SKU-001", 20));
+
+        agent.tick().await.unwrap();
+
+        assert_eq!(
+            *agent.commitments.supplier_requests.lock().unwrap(),
+            [(SUPPLIER.to_string(), "SKU-001".to_string(), 20.0)]
+        );
+        assert_eq!(
+            *agent.messaging.sent.lock().unwrap(),
+            [SUPPLIER.to_string()]
+        );
+        assert_eq!(agent.commitments.obligations.lock().unwrap().len(), 1);
+        let committed = events_of_kind(&agent, DomainEventKind::ReplenishmentCommitted).await;
+        assert_eq!(committed.len(), 1);
+        assert_eq!(committed[0]["request_id"], json!(request_id));
+        assert_eq!(committed[0]["total_amount"], json!("500"));
+    }
+
+    #[tokio::test]
+    async fn procurement_tick_dispatches_a_redelivered_commitment_once() {
+        let request_id = Uuid::new_v4();
+        let agent = procurement_agent(0.0, Vec::new(), request_id);
+        for _ in 0..2 {
+            agent
+                .append_event(
+                    DomainEventKind::InventoryShortage,
+                    &ShortagePayload {
+                        item_code: "SKU-001".to_string(),
+                        shortage_qty: 5.0,
+                    },
+                )
+                .await
+                .unwrap();
+        }
+        let reply = supplier_reply(request_id, "SKU-001", 5);
+        agent
+            .messaging
+            .inbox
+            .lock()
+            .unwrap()
+            .extend([reply.clone(), reply]);
+
+        agent.tick().await.unwrap();
+
+        assert_eq!(agent.commitments.obligations.lock().unwrap().len(), 1);
+        let committed = events_of_kind(&agent, DomainEventKind::ReplenishmentCommitted).await;
+        assert_eq!(committed.len(), 2);
+        assert_eq!(committed[0]["obligation_id"], committed[1]["obligation_id"]);
+        assert!(agent.outstanding_shortages().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn procurement_tick_ignores_stock_above_the_reorder_point() {
+        let agent = procurement_agent(
+            50.0,
+            vec![ReorderRule {
+                item_code: "SKU-001".to_string(),
+                reorder_point: 10.0,
+                reorder_quantity: 20.0,
+            }],
+            Uuid::new_v4(),
+        );
+
+        agent.tick().await.unwrap();
+
+        assert!(
+            agent
+                .commitments
+                .supplier_requests
+                .lock()
+                .unwrap()
+                .is_empty()
+        );
+        assert!(agent.messaging.sent.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn procurement_tick_holds_a_commitment_above_the_threshold_until_it_is_approved() {
+        let request_id = Uuid::new_v4();
+        let agent = procurement_agent(0.0, Vec::new(), request_id);
+        record_shortage(&agent, 500.0).await;
+        agent
+            .messaging
+            .inbox
+            .lock()
+            .unwrap()
+            .push(supplier_reply(request_id, "SKU-001", 500));
+
+        agent.tick().await.unwrap();
+
+        let escalations = agent.commitments.escalations.lock().unwrap().clone();
+        assert_eq!(escalations.len(), 1);
+        assert_eq!(escalations[0].0, SUPPLIER_COMMITMENT_APPROVAL);
+        assert_eq!(
+            escalations[0].1["commitment"]["request_id"],
+            json!(request_id)
+        );
+        assert_eq!(
+            escalations[0].1["commitment"]["total_amount"],
+            json!("12500")
+        );
+        assert!(agent.commitments.obligations.lock().unwrap().is_empty());
+
+        agent.tick().await.unwrap();
+
+        assert_eq!(agent.commitments.escalations.lock().unwrap().len(), 1);
+        assert!(agent.commitments.obligations.lock().unwrap().is_empty());
+        assert_eq!(agent.outstanding_shortages().await.unwrap().len(), 1);
+
+        *agent.commitments.escalation_status.lock().unwrap() = "APPROVED";
+        agent.tick().await.unwrap();
+
+        assert_eq!(agent.commitments.obligations.lock().unwrap().len(), 1);
+        let committed = events_of_kind(&agent, DomainEventKind::ReplenishmentCommitted).await;
+        assert_eq!(committed.len(), 1);
+        assert_eq!(committed[0]["requires_approval"], json!(true));
+        assert!(agent.outstanding_shortages().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn procurement_tick_drops_a_rejected_commitment_without_an_obligation() {
+        let request_id = Uuid::new_v4();
+        let agent = procurement_agent(0.0, Vec::new(), request_id);
+        record_shortage(&agent, 500.0).await;
+        agent
+            .messaging
+            .inbox
+            .lock()
+            .unwrap()
+            .push(supplier_reply(request_id, "SKU-001", 500));
+        agent.tick().await.unwrap();
+
+        *agent.commitments.escalation_status.lock().unwrap() = "REJECTED";
+        agent.tick().await.unwrap();
+
+        assert!(agent.commitments.obligations.lock().unwrap().is_empty());
+        assert!(
+            events_of_kind(&agent, DomainEventKind::ReplenishmentCommitted)
+                .await
+                .is_empty()
+        );
+        assert_eq!(
+            events_of_kind(&agent, DomainEventKind::ReplenishmentRejected)
+                .await
+                .len(),
+            1
+        );
+        assert!(agent.outstanding_shortages().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn procurement_tick_does_not_wait_for_the_supplier_and_commits_on_a_later_tick() {
+        let request_id = Uuid::new_v4();
+        let mut agent = procurement_agent(0.0, Vec::new(), request_id);
+        agent.config.max_wait_seconds = 3_600;
+        record_shortage(&agent, 5.0).await;
+
+        tokio::time::timeout(Duration::from_secs(1), agent.tick())
+            .await
+            .expect("the tick should not wait for a supplier reply")
+            .unwrap();
+
+        assert_eq!(agent.commitments.supplier_requests.lock().unwrap().len(), 1);
+        assert!(agent.commitments.obligations.lock().unwrap().is_empty());
+        assert!(
+            events_of_kind(&agent, DomainEventKind::ReplenishmentNegotiationFailed)
+                .await
+                .is_empty()
+        );
+
+        agent
+            .messaging
+            .inbox
+            .lock()
+            .unwrap()
+            .push(supplier_reply(request_id, "SKU-001", 5));
+        agent.tick().await.unwrap();
+
+        assert_eq!(agent.commitments.supplier_requests.lock().unwrap().len(), 1);
+        assert_eq!(agent.commitments.obligations.lock().unwrap().len(), 1);
+        assert!(agent.outstanding_shortages().await.unwrap().is_empty());
+    }
+
+    const PROCUREMENT_INBOX: &str = "procurement-agent";
+
+    /// Answers reservations from a script; once it runs out every call reserves.
+    #[derive(Default)]
+    struct ScriptedInventory {
+        script: Mutex<VecDeque<Result<Reservation, String>>>,
+        attempts: Mutex<u32>,
+    }
+
+    impl ScriptedInventory {
+        fn answering(script: impl IntoIterator<Item = Result<Reservation, String>>) -> Self {
+            Self {
+                script: Mutex::new(script.into_iter().collect()),
+                attempts: Mutex::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl InventoryTool for ScriptedInventory {
+        async fn quantity_available(&self, _item_code: &str) -> Result<f64> {
+            Ok(0.0)
+        }
+
+        async fn reserve_quantity(&self, _item_code: &str, _quantity: f64) -> Result<Reservation> {
+            *self.attempts.lock().unwrap() += 1;
+            match self.script.lock().unwrap().pop_front() {
+                Some(Ok(reservation)) => Ok(reservation),
+                Some(Err(reason)) => Err(anyhow::anyhow!(reason)),
+                None => Ok(Reservation::Reserved {
+                    reservation_id: Uuid::new_v4(),
+                }),
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct FakeOrders {
+        fulfilled: Mutex<Vec<Uuid>>,
+        awaiting_stock: Mutex<Vec<OutOfStockEvent>>,
+    }
+
+    #[async_trait]
+    impl OrderTool for FakeOrders {
+        async fn order_line(&self, order_id: Uuid) -> Result<OrderLine> {
+            Ok(OrderLine {
+                order_id,
+                item_code: "SKU-001".to_string(),
+                quantity: 5.0,
+            })
+        }
+
+        async fn fulfill_order(&self, order_id: Uuid) -> Result<()> {
+            self.fulfilled.lock().unwrap().push(order_id);
+            Ok(())
+        }
+
+        async fn mark_awaiting_stock(&self, stockout: &OutOfStockEvent) -> Result<()> {
+            self.awaiting_stock.lock().unwrap().push(stockout.clone());
+            Ok(())
+        }
+    }
+
+    type TestWarehouse =
+        WarehouseAgent<ScriptedInventory, FakeMessaging, FakeOrders, InMemoryEventStore>;
+
+    fn warehouse_agent(
+        inventory: ScriptedInventory,
+        events: InMemoryEventStore,
+        shortage_stream: Uuid,
+        checkpoint_stream: Uuid,
+        order_ids: &[Uuid],
+    ) -> TestWarehouse {
+        let created = order_ids
+            .iter()
+            .map(|&order_id| {
+                Ok(OrderCreatedEvent {
+                    order_id,
+                    traceparent: None,
+                    tracestate: None,
+                    correlation_id: None,
+                })
+            })
+            .collect::<Vec<_>>();
+        WarehouseAgent::new(
+            inventory,
+            FakeMessaging::default(),
+            FakeOrders::default(),
+            events,
+            Box::pin(futures_util::stream::iter(created)),
+            shortage_stream,
+            WarehouseConfig {
+                procurement_recipient: PROCUREMENT_INBOX.to_string(),
+                max_reservation_attempts: 3,
+                reservation_backoff: Duration::from_millis(1),
+                order_wait: Duration::ZERO,
+                forwarding_checkpoint_stream: checkpoint_stream,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn warehouse_fulfills_orders_it_reserves_after_transient_failures() {
+        let order_id = Uuid::new_v4();
+        let agent = warehouse_agent(
+            ScriptedInventory::answering([Err("inventory busy".to_string())]),
+            InMemoryEventStore::default(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            &[order_id],
+        );
+
+        agent.tick().await.unwrap();
+
+        assert_eq!(*agent.inventory.attempts.lock().unwrap(), 2);
+        assert_eq!(*agent.orders.fulfilled.lock().unwrap(), [order_id]);
+        assert!(agent.orders.awaiting_stock.lock().unwrap().is_empty());
+        assert!(agent.messaging.sent.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn warehouse_gives_up_on_a_failing_reservation_and_moves_to_the_next_order() {
+        let (failing, next) = (Uuid::new_v4(), Uuid::new_v4());
+        let agent = warehouse_agent(
+            ScriptedInventory::answering(std::iter::repeat_n(
+                Err("inventory unavailable".to_string()),
+                3,
+            )),
+            InMemoryEventStore::default(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            &[failing, next],
+        );
+        let started = Instant::now();
+
+        agent.tick().await.unwrap();
+
+        assert_eq!(*agent.inventory.attempts.lock().unwrap(), 4);
+        assert!(started.elapsed() >= Duration::from_millis(1 + 2));
+        assert_eq!(*agent.orders.fulfilled.lock().unwrap(), [next]);
+        assert!(agent.orders.awaiting_stock.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn warehouse_parks_short_orders_and_forwards_each_shortage_once() {
+        let order_id = Uuid::new_v4();
+        let (shortage_stream, checkpoint_stream) = (Uuid::new_v4(), Uuid::new_v4());
+        let agent = warehouse_agent(
+            ScriptedInventory::answering([Ok(Reservation::InsufficientStock { available: 2.0 })]),
+            InMemoryEventStore::default(),
+            shortage_stream,
+            checkpoint_stream,
+            &[order_id],
+        );
+
+        agent.tick().await.unwrap();
+        agent.tick().await.unwrap();
+
+        assert!(agent.orders.fulfilled.lock().unwrap().is_empty());
+        let awaiting_stock = agent.orders.awaiting_stock.lock().unwrap().clone();
+        assert_eq!(awaiting_stock.len(), 1);
+        assert_eq!(awaiting_stock[0].order_id, order_id);
+        assert_eq!(awaiting_stock[0].item_code, "SKU-001");
+        assert_eq!(awaiting_stock[0].requested_quantity, 5.0);
+        assert_eq!(awaiting_stock[0].available_quantity, 2.0);
+
+        let shortages = agent.events.stream(shortage_stream).await.unwrap();
+        assert_eq!(shortages.len(), 1);
+        assert!(matches!(
+            shortages[0].event.kind,
+            DomainEventKind::InventoryShortage
+        ));
+        assert_eq!(shortages[0].event.payload["shortage_qty"], json!(3.0));
+        assert_eq!(
+            *agent.messaging.sent.lock().unwrap(),
+            [PROCUREMENT_INBOX.to_string()]
+        );
+
+        // A restarted agent resumes from the stored checkpoint instead of re-sending history.
+        let restarted = warehouse_agent(
+            ScriptedInventory::default(),
+            agent.events,
+            shortage_stream,
+            checkpoint_stream,
+            &[],
+        );
+        restarted.tick().await.unwrap();
+        assert!(restarted.messaging.sent.lock().unwrap().is_empty());
+    }
+
+    struct FakePeriodClose {
+        debits: Decimal,
+        reconciliation_status: &'static str,
+        allocation_fails: bool,
+        calls: Mutex<Vec<String>>,
+        escalations: Mutex<Vec<(String, Value)>>,
+        logs: Mutex<Vec<PeriodCloseLog>>,
+    }
+
+    impl FakePeriodClose {
+        fn new() -> Self {
+            Self {
+                debits: Decimal::from(1_000),
+                reconciliation_status: "BALANCED",
+                allocation_fails: false,
+                calls: Mutex::default(),
+                escalations: Mutex::default(),
+                logs: Mutex::default(),
+            }
+        }
+
+        fn record(&self, call: impl Into<String>) {
+            self.calls.lock().unwrap().push(call.into());
+        }
+    }
+
+    #[async_trait]
+    impl PeriodCloseTool for FakePeriodClose {
+        async fn trial_balance(&self, as_of: NaiveDate) -> Result<TrialBalanceTotals> {
+            self.record(format!("trial_balance {as_of}"));
+            Ok(TrialBalanceTotals {
+                total_debits: self.debits,
+                total_credits: Decimal::from(1_000),
+            })
+        }
+
+        async fn run_allocation(
+            &self,
+            period_start: NaiveDate,
+            period_end: NaiveDate,
+        ) -> Result<()> {
+            self.record(format!("run_allocation {period_start} {period_end}"));
+            if self.allocation_fails {
+                anyhow::bail!("allocation returned 409");
+            }
+            Ok(())
+        }
+
+        async fn reconciliation_status(
+            &self,
+            _period_start: NaiveDate,
+            _period_end: NaiveDate,
+        ) -> Result<String> {
+            self.record("reconciliation_status");
+            Ok(self.reconciliation_status.to_string())
+        }
+
+        async fn active_kpi_targets(
+            &self,
+            _period_start: NaiveDate,
+            _period_end: NaiveDate,
+        ) -> Result<Vec<KpiTargetKey>> {
+            self.record("active_kpi_targets");
+            Ok(["REVENUE", "GROSS_MARGIN"]
+                .into_iter()
+                .map(|metric_name| KpiTargetKey {
+                    business_unit: "CORE".to_string(),
+                    mandate: "GROWTH".to_string(),
+                    metric_name: metric_name.to_string(),
+                })
+                .collect())
+        }
+
+        async fn evaluate_variance(
+            &self,
+            _period_start: NaiveDate,
+            _period_end: NaiveDate,
+            target: &KpiTargetKey,
+        ) -> Result<String> {
+            self.record(format!("evaluate_variance {}", target.metric_name));
+            Ok(if target.metric_name == "REVENUE" {
+                "BREACH".to_string()
+            } else {
+                "ON_TRACK".to_string()
+            })
+        }
+
+        async fn post_due_recurring_journals(
+            &self,
+            _period_start: NaiveDate,
+            _period_end: NaiveDate,
+        ) -> Result<usize> {
+            self.record("post_due_recurring_journals");
+            Ok(2)
+        }
+
+        async fn create_escalation(&self, action_type: &str, details: &Value) -> Result<Uuid> {
+            self.record("create_escalation");
+            self.escalations
+                .lock()
+                .unwrap()
+                .push((action_type.to_string(), details.clone()));
+            Ok(Uuid::new_v4())
+        }
+
+        async fn record_period_close(&self, log: &PeriodCloseLog) -> Result<Uuid> {
+            self.record("record_period_close");
+            self.logs.lock().unwrap().push(log.clone());
+            Ok(Uuid::new_v4())
+        }
+
+        async fn period_closed(
+            &self,
+            period_start: NaiveDate,
+            period_end: NaiveDate,
+        ) -> Result<bool> {
+            self.record("period_closed");
+            Ok(self.logs.lock().unwrap().iter().any(|log| {
+                log.period_start == period_start
+                    && log.period_end == period_end
+                    && log.steps.iter().all(|outcome| outcome.passed)
+            }))
+        }
+    }
+
+    fn controller(close: FakePeriodClose) -> ControllerAgent<FakePeriodClose> {
+        ControllerAgent::new(
+            close,
+            ControllerConfig {
+                close_day_of_month: 1,
+                tolerance_pct: Decimal::new(5, 1),
+            },
+        )
+    }
+
+    fn previous_month(today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let period_end = today.with_day(1).unwrap().pred_opt().unwrap();
+        (period_end.with_day(1).unwrap(), period_end)
+    }
+
+    #[tokio::test]
+    async fn controller_runs_the_full_checklist_in_order_once_per_period() {
+        let agent = controller(FakePeriodClose::new());
+        let (period_start, period_end) = previous_month(Utc::now().date_naive());
+
+        agent.tick().await.unwrap();
+        agent.tick().await.unwrap();
+
+        assert_eq!(
+            *agent.close.calls.lock().unwrap(),
+            vec![
+                "period_closed".to_string(),
+                format!("trial_balance {period_end}"),
+                format!("run_allocation {period_start} {period_end}"),
+                "reconciliation_status".to_string(),
+                "active_kpi_targets".to_string(),
+                "evaluate_variance REVENUE".to_string(),
+                "evaluate_variance GROSS_MARGIN".to_string(),
+                "post_due_recurring_journals".to_string(),
+                "record_period_close".to_string(),
+                "period_closed".to_string(),
+            ]
+        );
+        assert!(agent.close.escalations.lock().unwrap().is_empty());
+
+        let logs = agent.close.logs.lock().unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].period_start, period_start);
+        assert_eq!(logs[0].period_end, period_end);
+        assert_eq!(logs[0].escalation_id, None);
+        assert_eq!(
+            logs[0]
+                .steps
+                .iter()
+                .map(|outcome| (outcome.step.as_str(), outcome.passed))
+                .collect::<Vec<_>>(),
+            vec![
+                ("TRIAL_BALANCE", true),
+                ("FINOPS_ALLOCATION", true),
+                ("RECONCILIATION", true),
+                ("KPI_VARIANCE", true),
+                ("RECURRING_JOURNALS", true),
+            ]
+        );
+        assert_eq!(logs[0].steps[3].detail, "evaluated 2 targets, 1 breached");
+    }
+
+    #[tokio::test]
+    async fn controller_escalates_failed_steps_and_logs_the_escalation() {
+        let mut close = FakePeriodClose::new();
+        close.debits = Decimal::from(1_100);
+        close.allocation_fails = true;
+        close.reconciliation_status = "OUT_OF_TOLERANCE";
+        let agent = controller(close);
+
+        agent.tick().await.unwrap();
+
+        let calls = agent.close.calls.lock().unwrap();
+        assert_eq!(
+            calls[calls.len() - 2..],
+            ["create_escalation", "record_period_close"]
+        );
+
+        let escalations = agent.close.escalations.lock().unwrap();
+        assert_eq!(escalations.len(), 1);
+        let (action_type, details) = &escalations[0];
+        assert_eq!(action_type, PERIOD_CLOSE_BLOCKED);
+        let failed = details["failed_steps"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|outcome| outcome["step"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            failed,
+            ["TRIAL_BALANCE", "FINOPS_ALLOCATION", "RECONCILIATION"]
+        );
+        assert_eq!(
+            details["failed_steps"][1]["detail"],
+            "allocation returned 409"
+        );
+
+        let logs = agent.close.logs.lock().unwrap();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].escalation_id.is_some());
+        assert_eq!(logs[0].steps.len(), 5);
+        assert!(logs[0].steps[3].passed && logs[0].steps[4].passed);
+    }
+
+    #[tokio::test]
+    async fn controller_skips_a_period_closed_before_a_restart() {
+        let agent = controller(FakePeriodClose::new());
+        agent.tick().await.unwrap();
+
+        let close = FakePeriodClose::new();
+        *close.logs.lock().unwrap() = agent.close.logs.lock().unwrap().clone();
+        let restarted = controller(close);
+        restarted.tick().await.unwrap();
+
+        assert_eq!(*restarted.close.calls.lock().unwrap(), ["period_closed"]);
+        assert_eq!(restarted.close.logs.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn controller_retries_a_blocked_close_until_every_step_passes() {
+        let mut close = FakePeriodClose::new();
+        close.allocation_fails = true;
+        let agent = controller(close);
+        agent.tick().await.unwrap();
+
+        let close = FakePeriodClose::new();
+        *close.logs.lock().unwrap() = agent.close.logs.lock().unwrap().clone();
+        let restarted = controller(close);
+        restarted.tick().await.unwrap();
+        restarted.tick().await.unwrap();
+
+        let calls = restarted.close.calls.lock().unwrap();
+        assert_eq!(
+            calls
+                .iter()
+                .filter(|call| call.starts_with("run_allocation"))
+                .count(),
+            1
+        );
+        assert!(restarted.close.escalations.lock().unwrap().is_empty());
+        let logs = restarted.close.logs.lock().unwrap();
+        assert_eq!(logs.len(), 2);
+        assert!(logs[0].escalation_id.is_some());
+        assert!(logs[1].steps.iter().all(|outcome| outcome.passed));
+    }
+
+    const EVIDENCE_SNAPSHOT_HASH: &str =
+        "8cfeccd781778fad610b822ecb7c4db1f6942757c2594e7320678c819a53ffc2";
+
+    fn evidence_snapshot() -> Value {
+        json!({
+            "order": {
+                "status": "FULFILLED",
+                "id": "5b1d3c1e-2f4a-4e59-9a43-0c6f1f0d8a11",
+                "quantity": "2.0000",
+            },
+            "journals": [
+                { "account": "1100", "debit": "250.0000", "credit": "0" },
+                { "account": "4000", "debit": "0", "credit": "250.0000" },
+            ],
+            "timeline_warnings": [],
+        })
+    }
+
+    #[derive(Default)]
+    struct FakeAudit {
+        pending: Mutex<Vec<AuditRequest>>,
+        stored: Mutex<Vec<(Uuid, String)>>,
+        completed: Mutex<Vec<Uuid>>,
+    }
+
+    #[async_trait]
+    impl AuditTool for FakeAudit {
+        async fn pending_audit_requests(&self) -> Result<Vec<AuditRequest>> {
+            Ok(std::mem::take(&mut *self.pending.lock().unwrap()))
+        }
+
+        async fn order_evidence(&self, _order_id: Uuid) -> Result<Value> {
+            Ok(evidence_snapshot())
+        }
+
+        async fn store_evidence(
+            &self,
+            request: &AuditRequest,
+            evidence_hash: &str,
+            _evidence: &Value,
+        ) -> Result<Uuid> {
+            self.stored
+                .lock()
+                .unwrap()
+                .push((request.request_id, evidence_hash.to_string()));
+            Ok(Uuid::new_v4())
+        }
+
+        async fn complete_audit_request(&self, request_id: Uuid) -> Result<()> {
+            self.completed.lock().unwrap().push(request_id);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn evidence_hash_matches_the_known_snapshot() {
+        assert_eq!(
+            evidence_hash(&evidence_snapshot()).unwrap(),
+            EVIDENCE_SNAPSHOT_HASH
+        );
+
+        let mut tampered = evidence_snapshot();
+        tampered["journals"][0]["debit"] = json!("251.0000");
+        assert_ne!(evidence_hash(&tampered).unwrap(), EVIDENCE_SNAPSHOT_HASH);
+    }
+
+    #[tokio::test]
+    async fn audit_tick_stores_the_hash_records_the_event_and_completes_the_request() {
+        let request = AuditRequest {
+            request_id: Uuid::new_v4(),
+            order_id: Uuid::new_v4(),
+        };
+        let agent = AuditAgent {
+            audit: FakeAudit {
+                pending: Mutex::new(vec![request.clone()]),
+                ..FakeAudit::default()
+            },
+            events: InMemoryEventStore::default(),
+        };
+
+        agent.tick().await.unwrap();
+
+        assert_eq!(
+            *agent.audit.stored.lock().unwrap(),
+            vec![(request.request_id, EVIDENCE_SNAPSHOT_HASH.to_string())]
+        );
+        assert_eq!(
+            *agent.audit.completed.lock().unwrap(),
+            vec![request.request_id]
+        );
+        let events = agent.events.stream(request.order_id).await.unwrap();
+        assert_eq!(events.len(), 1);
+        let DomainEventKind::AuditEvidenceGenerated {
+            order_id,
+            evidence_hash,
+        } = &events[0].event.kind
+        else {
+            panic!("unexpected event kind {:?}", events[0].event.kind);
+        };
+        assert_eq!(*order_id, request.order_id);
+        assert_eq!(evidence_hash, EVIDENCE_SNAPSHOT_HASH);
+    }
+
+    async fn record_shortage(
+        agent: &ProcurementAgent<FakeMessaging, FakeInventory, FakeCommitments, InMemoryEventStore>,
+        shortage_qty: f64,
+    ) {
+        agent
+            .append_event(
+                DomainEventKind::InventoryShortage,
+                &ShortagePayload {
+                    item_code: "SKU-001".to_string(),
+                    shortage_qty,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn procurement_rejects_a_short_commitment_and_keeps_the_shortage_open() {
+        let request_id = Uuid::new_v4();
+        let agent = procurement_agent(0.0, Vec::new(), request_id);
+        record_shortage(&agent, 20.0).await;
+        agent
+            .messaging
+            .inbox
+            .lock()
+            .unwrap()
+            .push(supplier_reply(request_id, "SKU-001", 10));
+
+        agent.tick().await.unwrap();
+
+        assert!(agent.commitments.obligations.lock().unwrap().is_empty());
+        assert!(
+            events_of_kind(&agent, DomainEventKind::ReplenishmentCommitted)
+                .await
+                .is_empty()
+        );
+        let failed = events_of_kind(&agent, DomainEventKind::ReplenishmentNegotiationFailed).await;
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0]["request_id"], json!(request_id));
+        assert_eq!(
+            failed[0]["reason"],
+            json!("committed quantity 10 is below shortage 20")
+        );
+        // The replenishment request and the failure notice both went to the supplier.
+        assert_eq!(
+            *agent.messaging.sent.lock().unwrap(),
+            [SUPPLIER.to_string(), SUPPLIER.to_string()]
+        );
+        assert_eq!(agent.outstanding_shortages().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn procurement_counter_offers_an_overpriced_commitment_then_accepts() {
+        let request_id = Uuid::new_v4();
+        let mut agent = procurement_agent(0.0, Vec::new(), request_id);
+        agent.config.max_unit_price = Some(Decimal::new(20, 0));
+        agent.config.max_counter_offers = 1;
+        record_shortage(&agent, 5.0).await;
+        agent.messaging.inbox.lock().unwrap().extend([
+            supplier_offer(request_id, "SKU-001", 5, 25),
+            supplier_offer(request_id, "SKU-001", 5, 20),
+        ]);
+
+        agent.tick().await.unwrap();
+
+        assert_eq!(agent.messaging.sent.lock().unwrap().len(), 2);
+        assert!(agent.commitments.obligations.lock().unwrap().is_empty());
+        let countered = events_of_kind(&agent, DomainEventKind::ReplenishmentCounterOffered).await;
+        assert_eq!(countered.len(), 1);
+        assert_eq!(countered[0]["counter_offers"], json!(1));
+
+        agent.tick().await.unwrap();
+
+        assert_eq!(agent.messaging.sent.lock().unwrap().len(), 2);
+        assert_eq!(agent.commitments.obligations.lock().unwrap().len(), 1);
+        let committed = events_of_kind(&agent, DomainEventKind::ReplenishmentCommitted).await;
+        assert_eq!(committed.len(), 1);
+        assert_eq!(committed[0]["total_amount"], json!("100"));
+        assert!(
+            events_of_kind(&agent, DomainEventKind::ReplenishmentNegotiationFailed)
+                .await
+                .is_empty()
+        );
+        assert!(agent.outstanding_shortages().await.unwrap().is_empty());
+    }
+
+    #[derive(Default)]
+    struct FakeReceivables {
+        uninvoiced: Mutex<Vec<Uuid>>,
+        items: Mutex<Vec<ArAgingItem>>,
+        stages: Mutex<HashMap<Uuid, CollectionStage>>,
+        escalated: Mutex<Vec<Uuid>>,
+    }
+
+    impl FakeReceivables {
+        fn age(&self, customer_email: &str, age_days: i64) {
+            let mut items = self.items.lock().unwrap();
+            match items
+                .iter_mut()
+                .find(|item| item.customer_email == customer_email)
+            {
+                Some(item) => item.age_days = age_days,
+                None => items.push(ArAgingItem {
+                    order_id: Uuid::new_v4(),
+                    invoice_id: Uuid::new_v4(),
+                    customer_email: customer_email.to_string(),
+                    outstanding: Decimal::from(100),
+                    currency: "USD".to_string(),
+                    age_days,
+                    collection_stage: None,
+                }),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ReceivablesTool for FakeReceivables {
+        async fn uninvoiced_fulfilled_orders(&self) -> Result<Vec<Uuid>> {
+            Ok(std::mem::take(&mut *self.uninvoiced.lock().unwrap()))
+        }
+
+        async fn generate_invoice(&self, _order_id: Uuid) -> Result<Uuid> {
+            Ok(Uuid::new_v4())
+        }
+
+        async fn aging_items(&self) -> Result<Vec<ArAgingItem>> {
+            let stages = self.stages.lock().unwrap();
+            Ok(self
+                .items
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|item| ArAgingItem {
+                    collection_stage: stages.get(&item.invoice_id).copied(),
+                    ..item.clone()
+                })
+                .collect())
+        }
+
+        async fn escalate_collection(&self, item: &ArAgingItem, action_type: &str) -> Result<Uuid> {
+            assert_eq!(action_type, AR_COLLECTION_BREACH);
+            self.escalated.lock().unwrap().push(item.invoice_id);
+            Ok(Uuid::new_v4())
+        }
+
+        async fn record_collection_stage(
+            &self,
+            invoice_id: Uuid,
+            stage: CollectionStage,
+        ) -> Result<()> {
+            self.stages.lock().unwrap().insert(invoice_id, stage);
+            Ok(())
+        }
+    }
+
+    fn ar_agent() -> ArAgent<FakeMessaging, FakeReceivables, InMemoryEventStore> {
+        ArAgent {
+            messaging: FakeMessaging::default(),
+            receivables: FakeReceivables::default(),
+            events: InMemoryEventStore::default(),
+            stream_id: Uuid::new_v4(),
+            config: ArAgentConfig {
+                reminder_days: 30,
+                escalation_days: 60,
+            },
+        }
+    }
+
+    async fn ar_tick_summaries(
+        agent: &ArAgent<FakeMessaging, FakeReceivables, InMemoryEventStore>,
+    ) -> Vec<Value> {
+        agent
+            .events
+            .stream(agent.stream_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|envelope| envelope.event.payload)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn ar_tick_invoices_reminds_and_escalates_by_aging_stage() {
+        let agent = ar_agent();
+        agent
+            .receivables
+            .uninvoiced
+            .lock()
+            .unwrap()
+            .push(Uuid::new_v4());
+        agent.receivables.age("current@example.com", 10);
+        agent.receivables.age("late@example.com", 45);
+        agent.receivables.age("delinquent@example.com", 75);
+
+        agent.tick().await.unwrap();
+
+        assert_eq!(
+            *agent.messaging.sent.lock().unwrap(),
+            ["late@example.com", "delinquent@example.com"]
+        );
+        let items = agent.receivables.items.lock().unwrap().clone();
+        assert_eq!(
+            *agent.receivables.escalated.lock().unwrap(),
+            [items[2].invoice_id]
+        );
+        let stages = agent.receivables.stages.lock().unwrap().clone();
+        assert_eq!(stages.get(&items[0].invoice_id), None);
+        assert_eq!(
+            stages.get(&items[1].invoice_id),
+            Some(&CollectionStage::Reminded)
+        );
+        assert_eq!(
+            stages.get(&items[2].invoice_id),
+            Some(&CollectionStage::Escalated)
+        );
+        let summaries = ar_tick_summaries(&agent).await;
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0]["invoices_raised"].as_array().unwrap().len(), 1);
+        assert_eq!(summaries[0]["reminders_sent"], 2);
+        assert_eq!(
+            summaries[0]["escalations_created"]
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn ar_tick_acts_only_when_an_invoice_reaches_a_new_stage() {
+        let agent = ar_agent();
+        agent.receivables.age("late@example.com", 45);
+        agent.receivables.age("delinquent@example.com", 75);
+        agent.tick().await.unwrap();
+
+        agent.tick().await.unwrap();
+        assert_eq!(agent.messaging.sent.lock().unwrap().len(), 2);
+        assert_eq!(agent.receivables.escalated.lock().unwrap().len(), 1);
+
+        agent.receivables.age("late@example.com", 61);
+        agent.tick().await.unwrap();
+        agent.tick().await.unwrap();
+
+        assert_eq!(
+            agent.messaging.sent.lock().unwrap().len(),
+            2,
+            "no second reminder once escalated"
+        );
+        let late_invoice = agent.receivables.items.lock().unwrap()[0].invoice_id;
+        assert_eq!(agent.receivables.escalated.lock().unwrap().len(), 2);
+        assert_eq!(agent.receivables.escalated.lock().unwrap()[1], late_invoice);
+        let summaries = ar_tick_summaries(&agent).await;
+        let reminders: Vec<i64> = summaries
+            .iter()
+            .map(|summary| summary["reminders_sent"].as_i64().unwrap())
+            .collect();
+        let escalations: Vec<usize> = summaries
+            .iter()
+            .map(|summary| summary["escalations_created"].as_array().unwrap().len())
+            .collect();
+        assert_eq!(reminders, [2, 0, 0, 0]);
+        assert_eq!(escalations, [1, 0, 1, 0]);
+    }
+}
@@ -0,0 +1,646 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use uuid::Uuid;
+
+pub const A2A_SCHEMA_VERSION: &str = "1.0";
+pub const SUPPORTED_A2A_SCHEMA_VERSIONS: [&str; 1] = [A2A_SCHEMA_VERSION];
+const REQUIRED_FIELDS: [&str; 8] = [
+    "message_id",
+    "correlation_id",
+    "idempotency_key",
+    "schema_version",
+    "sender_agent_id",
+    "recipient_agent_id",
+    "payload",
+    "sent_at",
+];
+
+pub type A2ASchemaVersion = &'static str;
+pub type A2AMigration = fn(&Value) -> Value;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum A2AValidationError {
+    #[error("a2a message is not valid JSON: {0}")]
+    InvalidJson(String),
+    #[error("a2a message must be a JSON object")]
+    NotAnObject,
+    #[error("a2a message is missing required field {0}")]
+    MissingField(&'static str),
+    #[error("a2a field {0} must not be empty")]
+    EmptyField(&'static str),
+    #[error(
+        "a2a schema version {received_version} is not supported (supported: {supported_versions:?})"
+    )]
+    IncompatibleSchemaVersion {
+        received_version: String,
+        supported_versions: Vec<A2ASchemaVersion>,
+    },
+    #[error("a2a message does not match the expected shape: {0}")]
+    InvalidShape(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionCheckResult {
+    Compatible,
+    OlderButSupported(bool),
+    Incompatible,
+}
+
+#[derive(Debug, Clone)]
+pub struct VersionMatcher {
+    supported_versions: Vec<A2ASchemaVersion>,
+    current_version: A2ASchemaVersion,
+    migrations: HashMap<(A2ASchemaVersion, A2ASchemaVersion), A2AMigration>,
+}
+
+impl Default for VersionMatcher {
+    fn default() -> Self {
+        Self::new(SUPPORTED_A2A_SCHEMA_VERSIONS.to_vec())
+    }
+}
+
+impl VersionMatcher {
+    pub fn new(supported_versions: Vec<A2ASchemaVersion>) -> Self {
+        let current_version = supported_versions
+            .iter()
+            .copied()
+            .filter_map(|version| parse_schema_version(version).map(|parsed| (parsed, version)))
+            .max()
+            .map(|(_, version)| version)
+            .unwrap_or(A2A_SCHEMA_VERSION);
+        Self {
+            supported_versions,
+            current_version,
+            migrations: HashMap::new(),
+        }
+    }
+
+    pub fn with_migration(
+        mut self,
+        from_version: A2ASchemaVersion,
+        to_version: A2ASchemaVersion,
+        migration: A2AMigration,
+    ) -> Self {
+        self.migrations
+            .insert((from_version, to_version), migration);
+        self
+    }
+
+    pub fn current_version(&self) -> A2ASchemaVersion {
+        self.current_version
+    }
+
+    pub fn supported_versions(&self) -> &[A2ASchemaVersion] {
+        &self.supported_versions
+    }
+
+    pub fn check(&self, version: &str) -> VersionCheckResult {
+        if version == self.current_version {
+            return VersionCheckResult::Compatible;
+        }
+        let (Some(received), Some(current)) = (
+            parse_schema_version(version),
+            parse_schema_version(self.current_version),
+        ) else {
+            return VersionCheckResult::Incompatible;
+        };
+        if received.0 != current.0 || received >= current {
+            return VersionCheckResult::Incompatible;
+        }
+
+        match self.migration_path(version) {
+            Some(path) => VersionCheckResult::OlderButSupported(!path.is_empty()),
+            None => VersionCheckResult::Incompatible,
+        }
+    }
+
+    pub fn migrate(&self, message: &Value, from_version: &str) -> Value {
+        let mut migrated = message.clone();
+        for migration in self.migration_path(from_version).unwrap_or_default() {
+            migrated = migration(&migrated);
+        }
+        migrated
+    }
+
+    fn migration_path(&self, from_version: &str) -> Option<Vec<A2AMigration>> {
+        let mut path = Vec::new();
+        let mut version = from_version;
+        while version != self.current_version {
+            let Some(floor) = parse_schema_version(version) else {
+                break;
+            };
+            let Some(((_, to_version), migration)) = self
+                .migrations
+                .iter()
+                .filter(|((from, to), _)| {
+                    *from == version && parse_schema_version(to).is_some_and(|to| to > floor)
+                })
+                .min_by_key(|((_, to), _)| parse_schema_version(to))
+            else {
+                break;
+            };
+            path.push(*migration);
+            version = to_version;
+        }
+
+        if version == self.current_version || self.supported_versions.contains(&version) {
+            Some(path)
+        } else if self.supported_versions.contains(&from_version) {
+            Some(Vec::new())
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct A2AMessage<T> {
+    pub message_id: Uuid,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub idempotency_key: String,
+    #[serde(
+        default = "default_schema_version",
+        deserialize_with = "deserialize_schema_version"
+    )]
+    pub schema_version: A2ASchemaVersion,
+    pub sender_agent_id: String,
+    pub recipient_agent_id: String,
+    pub payload: T,
+    pub sent_at: DateTime<Utc>,
+    #[serde(default)]
+    pub replay_count: u32,
+}
+
+impl<T> A2AMessage<T> {
+    pub fn new(
+        sender_agent_id: &str,
+        recipient_agent_id: &str,
+        idempotency_key: &str,
+        payload: T,
+    ) -> Self {
+        let message_id = Uuid::new_v4();
+        Self {
+            message_id,
+            correlation_id: message_id,
+            causation_id: None,
+            idempotency_key: idempotency_key.to_string(),
+            schema_version: A2A_SCHEMA_VERSION,
+            sender_agent_id: sender_agent_id.to_string(),
+            recipient_agent_id: recipient_agent_id.to_string(),
+            payload,
+            sent_at: Utc::now(),
+            replay_count: 0,
+        }
+    }
+
+    pub fn reply<U>(&self, idempotency_key: &str, payload: U) -> A2AMessage<U> {
+        A2AMessage {
+            message_id: Uuid::new_v4(),
+            correlation_id: self.correlation_id,
+            causation_id: Some(self.message_id),
+            idempotency_key: idempotency_key.to_string(),
+            schema_version: A2A_SCHEMA_VERSION,
+            sender_agent_id: self.recipient_agent_id.clone(),
+            recipient_agent_id: self.sender_agent_id.clone(),
+            payload,
+            sent_at: Utc::now(),
+            replay_count: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplenishmentRequest {
+    pub item_code: String,
+    pub shortage_qty: Decimal,
+    pub currency: String,
+    pub requested_delivery_by: DateTime<Utc>,
+    pub max_unit_price: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SupplierCommitment {
+    pub item_code: String,
+    pub committed_qty: Decimal,
+    pub unit_price: Decimal,
+    pub currency: String,
+    pub estimated_delivery_at: DateTime<Utc>,
+    pub supplier_ref: String,
+    pub commitment_expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplenishmentNegotiationFailed {
+    pub item_code: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReplenishmentResponse {
+    Commitment(SupplierCommitment),
+    NegotiationFailed(ReplenishmentNegotiationFailed),
+}
+
+impl ReplenishmentRequest {
+    pub fn evaluate(&self, commitment: &SupplierCommitment) -> Result<(), String> {
+        if commitment.item_code != self.item_code {
+            return Err(format!(
+                "commitment is for {} but {} was requested",
+                commitment.item_code, self.item_code
+            ));
+        }
+        if !commitment.currency.eq_ignore_ascii_case(&self.currency) {
+            return Err(format!(
+                "commitment currency {} does not match {}",
+                commitment.currency, self.currency
+            ));
+        }
+        if commitment.committed_qty < self.shortage_qty {
+            return Err(format!(
+                "committed quantity {} is below shortage {}",
+                commitment.committed_qty, self.shortage_qty
+            ));
+        }
+        if let Some(max_unit_price) = self.max_unit_price
+            && commitment.unit_price > max_unit_price
+        {
+            return Err(format!(
+                "unit price {} exceeds maximum {}",
+                commitment.unit_price, max_unit_price
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub fn validate_a2a_message<T: DeserializeOwned>(
+    raw: &str,
+) -> Result<A2AMessage<T>, A2AValidationError> {
+    validate_a2a_message_with(raw, &VersionMatcher::default())
+}
+
+pub fn validate_a2a_message_with<T: DeserializeOwned>(
+    raw: &str,
+    matcher: &VersionMatcher,
+) -> Result<A2AMessage<T>, A2AValidationError> {
+    let value: Value = serde_json::from_str(raw)
+        .map_err(|err| A2AValidationError::InvalidJson(err.to_string()))?;
+    let object = value.as_object().ok_or(A2AValidationError::NotAnObject)?;
+
+    for field in REQUIRED_FIELDS {
+        if object.get(field).is_none_or(Value::is_null) {
+            return Err(A2AValidationError::MissingField(field));
+        }
+    }
+
+    let schema_version = object
+        .get("schema_version")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let mut value = match matcher.check(&schema_version) {
+        VersionCheckResult::Compatible | VersionCheckResult::OlderButSupported(false) => value,
+        VersionCheckResult::OlderButSupported(true) => matcher.migrate(&value, &schema_version),
+        VersionCheckResult::Incompatible => {
+            return Err(A2AValidationError::IncompatibleSchemaVersion {
+                received_version: schema_version,
+                supported_versions: matcher.supported_versions().to_vec(),
+            });
+        }
+    };
+    let object = value
+        .as_object_mut()
+        .ok_or(A2AValidationError::NotAnObject)?;
+
+    for field in ["idempotency_key", "sender_agent_id", "recipient_agent_id"] {
+        if object
+            .get(field)
+            .and_then(Value::as_str)
+            .is_some_and(|value| value.trim().is_empty())
+        {
+            return Err(A2AValidationError::EmptyField(field));
+        }
+    }
+
+    object.remove("schema_version");
+    let mut message: A2AMessage<T> = serde_json::from_value(value)
+        .map_err(|err| A2AValidationError::InvalidShape(err.to_string()))?;
+    message.schema_version = matcher.current_version();
+    Ok(message)
+}
+
+fn parse_schema_version(version: &str) -> Option<(u32, u32)> {
+    let (major, minor) = version.trim().split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+fn default_schema_version() -> A2ASchemaVersion {
+    A2A_SCHEMA_VERSION
+}
+
+fn deserialize_schema_version<'de, D>(deserializer: D) -> Result<A2ASchemaVersion, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let version = String::deserialize(deserializer)?;
+    SUPPORTED_A2A_SCHEMA_VERSIONS
+        .into_iter()
+        .find(|supported| *supported == version)
+        .ok_or_else(|| {
+            serde::de::Error::custom(format!("unsupported a2a schema version {version}"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use proptest::{collection::vec, prelude::*, sample::select};
+    use serde_json::json;
+
+    use super::*;
+
+    fn agent_id() -> impl Strategy<Value = String> {
+        vec(any::<char>(), 1..24)
+            .prop_map(|chars| chars.into_iter().collect::<String>())
+            .prop_filter("agent ids are not blank", |id| !id.trim().is_empty())
+    }
+
+    fn decimal() -> impl Strategy<Value = Decimal> {
+        (any::<i64>(), 0u32..=8).prop_map(|(mantissa, scale)| Decimal::new(mantissa, scale))
+    }
+
+    fn timestamp() -> impl Strategy<Value = DateTime<Utc>> {
+        (0i64..4_102_444_800, 0u32..1_000_000_000)
+            .prop_map(|(secs, nanos)| Utc.timestamp_opt(secs, nanos).unwrap())
+    }
+
+    prop_compose! {
+        fn replenishment_message()(
+            ids in any::<(u128, u128)>(),
+            causation_id in proptest::option::of(any::<u128>()),
+            idempotency_key in agent_id(),
+            sender_agent_id in agent_id(),
+            recipient_agent_id in agent_id(),
+            item_code in agent_id(),
+            shortage_qty in decimal(),
+            currency in agent_id(),
+            requested_delivery_by in timestamp(),
+            max_unit_price in proptest::option::of(decimal()),
+            sent_at in timestamp(),
+            replay_count in any::<u32>(),
+        ) -> A2AMessage<ReplenishmentRequest> {
+            A2AMessage {
+                message_id: Uuid::from_u128(ids.0),
+                correlation_id: Uuid::from_u128(ids.1),
+                causation_id: causation_id.map(Uuid::from_u128),
+                idempotency_key,
+                schema_version: A2A_SCHEMA_VERSION,
+                sender_agent_id,
+                recipient_agent_id,
+                payload: ReplenishmentRequest {
+                    item_code,
+                    shortage_qty,
+                    currency,
+                    requested_delivery_by,
+                    max_unit_price,
+                },
+                sent_at,
+                replay_count,
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn serialized_messages_round_trip_through_validation(message in replenishment_message()) {
+            let raw = serde_json::to_string(&message).unwrap();
+            let validated = validate_a2a_message::<ReplenishmentRequest>(&raw).unwrap();
+            prop_assert_eq!(validated, message);
+        }
+
+        #[test]
+        fn envelopes_missing_a_required_field_are_rejected(
+            message in replenishment_message(),
+            field in select(REQUIRED_FIELDS.to_vec()),
+            null_instead in any::<bool>(),
+        ) {
+            let mut value = serde_json::to_value(&message).unwrap();
+            let object = value.as_object_mut().unwrap();
+            if null_instead {
+                object.insert(field.to_string(), Value::Null);
+            } else {
+                object.remove(field);
+            }
+
+            prop_assert_eq!(
+                validate_a2a_message::<ReplenishmentRequest>(&value.to_string()),
+                Err(A2AValidationError::MissingField(field))
+            );
+        }
+    }
+
+    fn envelope() -> Value {
+        serde_json::to_value(A2AMessage::new(
+            "procurement-agent",
+            "supplier-agent",
+            "shortage-1",
+            json!({"item_code": "SKU-1"}),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn accepts_a_well_formed_envelope_and_defaults_replay_count() {
+        let mut value = envelope();
+        value.as_object_mut().unwrap().remove("replay_count");
+
+        let message = validate_a2a_message::<Value>(&value.to_string()).unwrap();
+
+        assert_eq!(message.sender_agent_id, "procurement-agent");
+        assert_eq!(message.recipient_agent_id, "supplier-agent");
+        assert_eq!(message.schema_version, A2A_SCHEMA_VERSION);
+        assert_eq!(message.replay_count, 0);
+        assert_eq!(message.payload, json!({"item_code": "SKU-1"}));
+    }
+
+    #[test]
+    fn rejects_malformed_json_and_non_objects() {
+        assert!(matches!(
+            validate_a2a_message::<Value>("{not json"),
+            Err(A2AValidationError::InvalidJson(_))
+        ));
+        assert_eq!(
+            validate_a2a_message::<Value>("[1, 2]"),
+            Err(A2AValidationError::NotAnObject)
+        );
+    }
+
+    #[test]
+    fn rejects_blank_identity_fields() {
+        for field in ["idempotency_key", "sender_agent_id", "recipient_agent_id"] {
+            let mut value = envelope();
+            value[field] = json!("  ");
+
+            assert_eq!(
+                validate_a2a_message::<Value>(&value.to_string()),
+                Err(A2AValidationError::EmptyField(field))
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_schema_versions() {
+        let mut value = envelope();
+        value["schema_version"] = json!("2.0");
+
+        assert_eq!(
+            validate_a2a_message::<Value>(&value.to_string()),
+            Err(A2AValidationError::IncompatibleSchemaVersion {
+                received_version: "2.0".to_string(),
+                supported_versions: SUPPORTED_A2A_SCHEMA_VERSIONS.to_vec(),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_payloads_of_the_wrong_shape() {
+        let mut value = envelope();
+        value["payload"] = json!({"item_code": 42});
+
+        assert!(matches!(
+            validate_a2a_message::<ReplenishmentRequest>(&value.to_string()),
+            Err(A2AValidationError::InvalidShape(_))
+        ));
+    }
+
+    fn rename_qty(message: &Value) -> Value {
+        let mut migrated = message.clone();
+        if let Some(qty) = migrated["payload"]
+            .as_object_mut()
+            .and_then(|payload| payload.remove("qty"))
+        {
+            migrated["payload"]["shortage_qty"] = qty;
+        }
+        migrated
+    }
+
+    fn default_currency(message: &Value) -> Value {
+        let mut migrated = message.clone();
+        if migrated["payload"].get("currency").is_none() {
+            migrated["payload"]["currency"] = json!("USD");
+        }
+        migrated
+    }
+
+    #[test]
+    fn version_matcher_treats_the_newest_supported_version_as_current() {
+        let matcher = VersionMatcher::new(vec!["1.0", "1.2", "1.1"]);
+
+        assert_eq!(matcher.current_version(), "1.2");
+        assert_eq!(matcher.check("1.2"), VersionCheckResult::Compatible);
+        assert_eq!(
+            VersionMatcher::default().check(A2A_SCHEMA_VERSION),
+            VersionCheckResult::Compatible
+        );
+    }
+
+    #[test]
+    fn version_matcher_accepts_older_minor_versions() {
+        let matcher =
+            VersionMatcher::new(vec!["1.0", "1.1", "1.2"]).with_migration("1.0", "1.1", rename_qty);
+
+        assert_eq!(
+            matcher.check("1.1"),
+            VersionCheckResult::OlderButSupported(false)
+        );
+        assert_eq!(
+            matcher.check("1.0"),
+            VersionCheckResult::OlderButSupported(true)
+        );
+    }
+
+    #[test]
+    fn version_matcher_accepts_unlisted_older_versions_only_with_a_migration() {
+        let without = VersionMatcher::new(vec!["1.3"]);
+        let with = VersionMatcher::new(vec!["1.3"]).with_migration("1.1", "1.3", rename_qty);
+
+        assert_eq!(without.check("1.1"), VersionCheckResult::Incompatible);
+        assert_eq!(
+            with.check("1.1"),
+            VersionCheckResult::OlderButSupported(true)
+        );
+    }
+
+    #[test]
+    fn version_matcher_keeps_natively_supported_versions_when_a_chain_is_incomplete() {
+        let matcher =
+            VersionMatcher::new(vec!["1.0", "1.2"]).with_migration("1.0", "1.1", rename_qty);
+        let message = json!({"payload": {"qty": "4"}});
+
+        assert_eq!(
+            matcher.check("1.0"),
+            VersionCheckResult::OlderButSupported(false)
+        );
+        assert_eq!(matcher.migrate(&message, "1.0"), message);
+    }
+
+    #[test]
+    fn version_matcher_rejects_other_majors_newer_and_malformed_versions() {
+        let matcher =
+            VersionMatcher::new(vec!["1.0", "1.1"]).with_migration("0.9", "1.1", rename_qty);
+
+        for version in ["0.9", "2.0", "1.2", "1", "v1.0", ""] {
+            assert_eq!(
+                matcher.check(version),
+                VersionCheckResult::Incompatible,
+                "{version}"
+            );
+        }
+    }
+
+    #[test]
+    fn version_matcher_chains_migrations_to_the_current_version() {
+        let matcher = VersionMatcher::new(vec!["1.0", "1.1", "1.2"])
+            .with_migration("1.1", "1.2", default_currency)
+            .with_migration("1.0", "1.1", rename_qty);
+        let message = json!({"payload": {"qty": "4"}});
+
+        assert_eq!(
+            matcher.migrate(&message, "1.0"),
+            json!({"payload": {"shortage_qty": "4", "currency": "USD"}})
+        );
+        assert_eq!(
+            matcher.migrate(&message, "1.1"),
+            json!({"payload": {"qty": "4", "currency": "USD"}})
+        );
+        assert_eq!(matcher.migrate(&message, "1.2"), message);
+    }
+
+    #[test]
+    fn validation_migrates_older_envelopes_before_deserializing() {
+        let matcher =
+            VersionMatcher::new(vec!["1.0", "1.1"]).with_migration("1.0", "1.1", rename_qty);
+        let mut value = envelope();
+        value["schema_version"] = json!("1.0");
+        value["payload"] = json!({
+            "item_code": "SKU-1",
+            "qty": "5",
+            "currency": "USD",
+            "requested_delivery_by": "2026-01-01T00:00:00Z",
+            "max_unit_price": null,
+        });
+
+        let message =
+            validate_a2a_message_with::<ReplenishmentRequest>(&value.to_string(), &matcher)
+                .unwrap();
+
+        assert_eq!(message.schema_version, "1.1");
+        assert_eq!(message.payload.shortage_qty, Decimal::new(5, 0));
+    }
+}
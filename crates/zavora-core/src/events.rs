@@ -1,7 +1,25 @@
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use uuid::Uuid;
 
+pub const DOMAIN_EVENT_SCHEMA_VERSION: u32 = 1;
+
+pub type EventUpcaster = fn(Value) -> Value;
+
+const EVENT_UPCASTERS: &[(u32, EventUpcaster)] = &[];
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum EventUpcastError {
+    #[error("domain event schema version {version} is newer than {current}")]
+    UnsupportedVersion { version: u32, current: u32 },
+    #[error("no upcaster registered for domain event schema version {0}")]
+    MissingUpcaster(u32),
+    #[error("domain event does not match the current shape: {0}")]
+    InvalidEvent(String),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DomainEventKind {
     CommitmentCreated,
@@ -11,6 +29,123 @@ pub enum DomainEventKind {
     InvoiceIssued,
     SettlementConfirmed,
     BoardActionFrozen,
+    InventoryShortage,
+    ReplenishmentRequested,
+    ReplenishmentCommitted,
+    ReplenishmentNegotiationFailed,
+    ReplenishmentCounterOffered,
+    ReplenishmentApprovalRequested,
+    ReplenishmentRejected,
+    InventoryShortagesForwarded,
+    OrderCreated,
+    OrderApproved,
+    OrderFulfilled,
+    OrderFailed,
+    EscalationOpened,
+    EscalationDecided,
+    ArAgentTickCompleted,
+    SkillStatusChanged,
+    SkillDeprecated,
+    GovernanceEscalationOverdue,
+    CorrectiveActionResolved,
+    CreditMemoIssued,
+    AuditEvidenceGenerated {
+        order_id: Uuid,
+        evidence_hash: String,
+    },
+}
+
+impl DomainEventKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CommitmentCreated => "CommitmentCreated",
+            Self::ObligationsAssigned => "ObligationsAssigned",
+            Self::StockReceived => "StockReceived",
+            Self::StockIssued => "StockIssued",
+            Self::InvoiceIssued => "InvoiceIssued",
+            Self::SettlementConfirmed => "SettlementConfirmed",
+            Self::BoardActionFrozen => "BoardActionFrozen",
+            Self::InventoryShortage => "InventoryShortage",
+            Self::ReplenishmentRequested => "ReplenishmentRequested",
+            Self::ReplenishmentCommitted => "ReplenishmentCommitted",
+            Self::ReplenishmentNegotiationFailed => "ReplenishmentNegotiationFailed",
+            Self::ReplenishmentCounterOffered => "ReplenishmentCounterOffered",
+            Self::ReplenishmentApprovalRequested => "ReplenishmentApprovalRequested",
+            Self::ReplenishmentRejected => "ReplenishmentRejected",
+            Self::InventoryShortagesForwarded => "InventoryShortagesForwarded",
+            Self::OrderCreated => "OrderCreated",
+            Self::OrderApproved => "OrderApproved",
+            Self::OrderFulfilled => "OrderFulfilled",
+            Self::OrderFailed => "OrderFailed",
+            Self::EscalationOpened => "EscalationOpened",
+            Self::EscalationDecided => "EscalationDecided",
+            Self::ArAgentTickCompleted => "ArAgentTickCompleted",
+            Self::SkillStatusChanged => "SkillStatusChanged",
+            Self::SkillDeprecated => "SkillDeprecated",
+            Self::GovernanceEscalationOverdue => "GovernanceEscalationOverdue",
+            Self::CorrectiveActionResolved => "CorrectiveActionResolved",
+            Self::CreditMemoIssued => "CreditMemoIssued",
+            Self::AuditEvidenceGenerated { .. } => "AuditEvidenceGenerated",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderCreatedPayload {
+    pub order_id: Uuid,
+    pub customer_email: String,
+    pub transaction_type: String,
+    pub item_code: String,
+    pub quantity: Decimal,
+    pub unit_price: Decimal,
+    pub currency: String,
+    pub status: String,
+    pub requested_by_agent_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderApprovedPayload {
+    pub order_id: Uuid,
+    pub escalation_id: Uuid,
+    pub approved_by_agent_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderFulfilledPayload {
+    pub order_id: Uuid,
+    pub settled_amount: Decimal,
+    pub currency: String,
+    pub fulfilled_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderFailedPayload {
+    pub order_id: Uuid,
+    pub failure_reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationOpenedPayload {
+    pub escalation_id: Uuid,
+    pub action_type: String,
+    pub reference_type: String,
+    pub reference_id: Uuid,
+    pub reason_code: String,
+    pub amount: Decimal,
+    pub currency: String,
+    pub requested_by_agent_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationDecidedPayload {
+    pub escalation_id: Uuid,
+    pub action_type: String,
+    pub reference_type: String,
+    pub reference_id: Uuid,
+    pub status: String,
+    pub decided_by_agent_id: Option<String>,
+    pub decision_note: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,3 +156,115 @@ pub struct DomainEvent {
     pub occurred_at: DateTime<Utc>,
     pub payload: serde_json::Value,
 }
+
+pub fn upcast(raw: Value, version: u32) -> Result<DomainEvent, EventUpcastError> {
+    upcast_with(EVENT_UPCASTERS, DOMAIN_EVENT_SCHEMA_VERSION, raw, version)
+}
+
+pub fn upcast_with(
+    upcasters: &[(u32, EventUpcaster)],
+    current: u32,
+    raw: Value,
+    version: u32,
+) -> Result<DomainEvent, EventUpcastError> {
+    if version > current {
+        return Err(EventUpcastError::UnsupportedVersion { version, current });
+    }
+
+    let mut event = raw;
+    for from_version in version..current {
+        let (_, upcaster) = upcasters
+            .iter()
+            .find(|(registered, _)| *registered == from_version)
+            .ok_or(EventUpcastError::MissingUpcaster(from_version))?;
+        event = upcaster(event);
+    }
+
+    serde_json::from_value(event).map_err(|err| EventUpcastError::InvalidEvent(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn v1_order_created() -> Value {
+        json!({
+            "id": Uuid::nil(),
+            "aggregate_id": Uuid::nil(),
+            "kind": "OrderCreated",
+            "occurred_at": "2026-01-01T00:00:00Z",
+            "payload": {"order_id": Uuid::nil(), "currency": "usd"},
+        })
+    }
+
+    fn rename_amount_to_total(mut event: Value) -> Value {
+        if let Some(payload) = event["payload"].as_object_mut()
+            && let Some(amount) = payload.remove("amount")
+        {
+            payload.insert("total".to_string(), amount);
+        }
+        event
+    }
+
+    fn uppercase_currency(mut event: Value) -> Value {
+        if let Some(currency) = event["payload"]["currency"].as_str() {
+            event["payload"]["currency"] = Value::String(currency.to_ascii_uppercase());
+        }
+        event
+    }
+
+    #[test]
+    fn current_version_events_deserialize_unchanged() {
+        let event = upcast(v1_order_created(), DOMAIN_EVENT_SCHEMA_VERSION).unwrap();
+        assert!(matches!(event.kind, DomainEventKind::OrderCreated));
+        assert_eq!(event.payload["currency"], "usd");
+    }
+
+    #[test]
+    fn older_events_run_each_registered_upcaster_in_order() {
+        let mut raw = v1_order_created();
+        raw["payload"]["amount"] = json!("12.50");
+        let upcasters: &[(u32, EventUpcaster)] =
+            &[(2, uppercase_currency), (1, rename_amount_to_total)];
+
+        let event = upcast_with(upcasters, 3, raw, 1).unwrap();
+        assert_eq!(event.payload["total"], "12.50");
+        assert!(event.payload.get("amount").is_none());
+        assert_eq!(event.payload["currency"], "USD");
+
+        let event = upcast_with(upcasters, 3, v1_order_created(), 2).unwrap();
+        assert_eq!(event.payload["currency"], "USD");
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let err = upcast(v1_order_created(), DOMAIN_EVENT_SCHEMA_VERSION + 1).unwrap_err();
+        assert_eq!(
+            err,
+            EventUpcastError::UnsupportedVersion {
+                version: DOMAIN_EVENT_SCHEMA_VERSION + 1,
+                current: DOMAIN_EVENT_SCHEMA_VERSION,
+            }
+        );
+    }
+
+    #[test]
+    fn gaps_in_the_upcaster_chain_are_reported() {
+        let upcasters: &[(u32, EventUpcaster)] = &[(1, uppercase_currency)];
+        assert_eq!(
+            upcast_with(upcasters, 3, v1_order_created(), 1).unwrap_err(),
+            EventUpcastError::MissingUpcaster(2)
+        );
+    }
+
+    #[test]
+    fn upcast_output_must_match_the_current_shape() {
+        let broken: &[(u32, EventUpcaster)] = &[(1, |_| json!({"kind": "OrderCreated"}))];
+        assert!(matches!(
+            upcast_with(broken, 2, v1_order_created(), 1),
+            Err(EventUpcastError::InvalidEvent(_))
+        ));
+    }
+}
@@ -1,9 +1,27 @@
+pub mod a2a;
 pub mod events;
 pub mod models;
+pub mod money;
+pub mod schemas;
 pub mod standards;
 pub mod storage;
 
-pub use events::{DomainEvent, DomainEventKind};
-pub use models::{Commitment, Obligation, Proof, Settlement};
+pub use a2a::{
+    A2A_SCHEMA_VERSION, A2AMessage, A2AMigration, A2ASchemaVersion, A2AValidationError,
+    ReplenishmentNegotiationFailed, ReplenishmentRequest, ReplenishmentResponse,
+    SUPPORTED_A2A_SCHEMA_VERSIONS, SupplierCommitment, VersionCheckResult, VersionMatcher,
+    validate_a2a_message, validate_a2a_message_with,
+};
+pub use events::{
+    DOMAIN_EVENT_SCHEMA_VERSION, DomainEvent, DomainEventKind, EscalationDecidedPayload,
+    EscalationOpenedPayload, EventUpcastError, EventUpcaster, OrderApprovedPayload,
+    OrderCreatedPayload, OrderFailedPayload, OrderFulfilledPayload, upcast, upcast_with,
+};
+pub use models::{
+    Commitment, CommitmentStatus, FulfillError, Obligation, ObligationStatus, Proof, SettleError,
+    Settlement,
+};
+pub use money::{Currency, Money, MoneyError};
+pub use schemas::{SCHEMA_VERSION, schemas};
 pub use standards::{ChartOfAccounts, IfrsLiteProfile, StandardsProfile};
-pub use storage::{EventEnvelope, EventStore, ProjectionStore};
+pub use storage::{EventEnvelope, EventStore, ProjectionStore, SnapshotStore};
@@ -1,9 +1,10 @@
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub enum CommitmentStatus {
     Draft,
     Active,
@@ -11,7 +12,36 @@ pub enum CommitmentStatus {
     Cancelled,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub enum ObligationStatus {
+    #[default]
+    Open,
+    Fulfilled,
+    Settled,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FulfillError {
+    #[error("commitment in status {0:?} cannot be fulfilled")]
+    InvalidStatus(CommitmentStatus),
+    #[error("proof {proof_id} is not linked to commitment {commitment_id}")]
+    ProofMismatch { proof_id: Uuid, commitment_id: Uuid },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SettleError {
+    #[error("obligation {0} is already settled")]
+    AlreadySettled(Uuid),
+    #[error("obligation {0} must be fulfilled before it can be settled")]
+    NotFulfilled(Uuid),
+    #[error("settlement {settlement_id} belongs to a different commitment than {commitment_id}")]
+    CommitmentMismatch {
+        settlement_id: Uuid,
+        commitment_id: Uuid,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Commitment {
     pub id: Uuid,
     pub commitment_type: String,
@@ -23,7 +53,7 @@ pub struct Commitment {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Obligation {
     pub id: Uuid,
     pub commitment_id: Uuid,
@@ -31,9 +61,15 @@ pub struct Obligation {
     pub due_at: DateTime<Utc>,
     pub depends_on: Vec<Uuid>,
     pub closed: bool,
+    #[serde(default)]
+    pub status: ObligationStatus,
+    #[serde(default)]
+    pub proof_id: Option<Uuid>,
+    #[serde(default)]
+    pub settlement_id: Option<Uuid>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Proof {
     pub id: Uuid,
     pub linked_id: Uuid,
@@ -42,7 +78,7 @@ pub struct Proof {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Settlement {
     pub id: Uuid,
     pub commitment_id: Uuid,
@@ -51,3 +87,213 @@ pub struct Settlement {
     pub status: String,
     pub created_at: DateTime<Utc>,
 }
+
+impl Commitment {
+    pub fn fulfill(&mut self, proof: Proof) -> Result<Obligation, FulfillError> {
+        if self.status != CommitmentStatus::Active {
+            return Err(FulfillError::InvalidStatus(self.status.clone()));
+        }
+        if proof.linked_id != self.id {
+            return Err(FulfillError::ProofMismatch {
+                proof_id: proof.id,
+                commitment_id: self.id,
+            });
+        }
+
+        self.status = CommitmentStatus::Fulfilled;
+        Ok(Obligation {
+            id: Uuid::new_v4(),
+            commitment_id: self.id,
+            owner: self.to_party.clone(),
+            due_at: proof.created_at,
+            depends_on: Vec::new(),
+            closed: false,
+            status: ObligationStatus::Fulfilled,
+            proof_id: Some(proof.id),
+            settlement_id: None,
+        })
+    }
+}
+
+impl Obligation {
+    pub fn settle(&mut self, settlement: &Settlement) -> Result<(), SettleError> {
+        match self.status {
+            ObligationStatus::Settled => return Err(SettleError::AlreadySettled(self.id)),
+            ObligationStatus::Open => return Err(SettleError::NotFulfilled(self.id)),
+            ObligationStatus::Fulfilled => {}
+        }
+        if settlement.commitment_id != self.commitment_id {
+            return Err(SettleError::CommitmentMismatch {
+                settlement_id: settlement.id,
+                commitment_id: self.commitment_id,
+            });
+        }
+
+        self.status = ObligationStatus::Settled;
+        self.settlement_id = Some(settlement.id);
+        self.closed = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active_commitment() -> Commitment {
+        Commitment {
+            id: Uuid::new_v4(),
+            commitment_type: "SERVICE_DELIVERY".to_string(),
+            from_party: "zavora".to_string(),
+            to_party: "customer-1".to_string(),
+            terms: "deliver onboarding workshop".to_string(),
+            risk_class: "LOW".to_string(),
+            status: CommitmentStatus::Active,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn proof_for(commitment: &Commitment) -> Proof {
+        Proof {
+            id: Uuid::new_v4(),
+            linked_id: commitment.id,
+            source: "ops-agent".to_string(),
+            payload_ref: "s3://proofs/workshop.pdf".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn settlement_for(commitment_id: Uuid) -> Settlement {
+        Settlement {
+            id: Uuid::new_v4(),
+            commitment_id,
+            amount: Decimal::new(150_000, 2),
+            currency: "USD".to_string(),
+            status: "COMPLETED".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn commitment_walks_through_fulfillment_and_settlement() {
+        let mut commitment = active_commitment();
+        let proof = proof_for(&commitment);
+        let proof_id = proof.id;
+
+        let mut obligation = commitment.fulfill(proof).unwrap();
+        assert_eq!(commitment.status, CommitmentStatus::Fulfilled);
+        assert_eq!(obligation.commitment_id, commitment.id);
+        assert_eq!(obligation.owner, "customer-1");
+        assert_eq!(obligation.status, ObligationStatus::Fulfilled);
+        assert_eq!(obligation.proof_id, Some(proof_id));
+        assert!(!obligation.closed);
+
+        let settlement = settlement_for(commitment.id);
+        obligation.settle(&settlement).unwrap();
+        assert_eq!(obligation.status, ObligationStatus::Settled);
+        assert_eq!(obligation.settlement_id, Some(settlement.id));
+        assert!(obligation.closed);
+    }
+
+    #[test]
+    fn fulfill_rejects_commitments_that_are_not_active() {
+        for status in [
+            CommitmentStatus::Draft,
+            CommitmentStatus::Fulfilled,
+            CommitmentStatus::Cancelled,
+        ] {
+            let mut commitment = active_commitment();
+            commitment.status = status.clone();
+            let proof = proof_for(&commitment);
+
+            assert_eq!(
+                commitment.fulfill(proof).unwrap_err(),
+                FulfillError::InvalidStatus(status.clone())
+            );
+            assert_eq!(commitment.status, status);
+        }
+    }
+
+    #[test]
+    fn fulfill_rejects_a_second_fulfillment() {
+        let mut commitment = active_commitment();
+        commitment.fulfill(proof_for(&commitment)).unwrap();
+
+        assert_eq!(
+            commitment.fulfill(proof_for(&commitment)).unwrap_err(),
+            FulfillError::InvalidStatus(CommitmentStatus::Fulfilled)
+        );
+    }
+
+    #[test]
+    fn fulfill_rejects_a_proof_for_another_commitment() {
+        let mut commitment = active_commitment();
+        let other = active_commitment();
+        let proof = proof_for(&other);
+        let proof_id = proof.id;
+
+        assert_eq!(
+            commitment.fulfill(proof).unwrap_err(),
+            FulfillError::ProofMismatch {
+                proof_id,
+                commitment_id: commitment.id,
+            }
+        );
+        assert_eq!(commitment.status, CommitmentStatus::Active);
+    }
+
+    #[test]
+    fn settle_rejects_an_obligation_that_is_still_open() {
+        let commitment = active_commitment();
+        let mut obligation = Obligation {
+            id: Uuid::new_v4(),
+            commitment_id: commitment.id,
+            owner: commitment.to_party.clone(),
+            due_at: Utc::now(),
+            depends_on: Vec::new(),
+            closed: false,
+            status: ObligationStatus::Open,
+            proof_id: None,
+            settlement_id: None,
+        };
+
+        assert_eq!(
+            obligation.settle(&settlement_for(commitment.id)),
+            Err(SettleError::NotFulfilled(obligation.id))
+        );
+        assert_eq!(obligation.status, ObligationStatus::Open);
+        assert_eq!(obligation.settlement_id, None);
+        assert!(!obligation.closed);
+    }
+
+    #[test]
+    fn settle_rejects_double_settlement() {
+        let mut commitment = active_commitment();
+        let mut obligation = commitment.fulfill(proof_for(&commitment)).unwrap();
+        let first = settlement_for(commitment.id);
+        obligation.settle(&first).unwrap();
+
+        assert_eq!(
+            obligation.settle(&settlement_for(commitment.id)),
+            Err(SettleError::AlreadySettled(obligation.id))
+        );
+        assert_eq!(obligation.settlement_id, Some(first.id));
+    }
+
+    #[test]
+    fn settle_rejects_a_settlement_for_another_commitment() {
+        let mut commitment = active_commitment();
+        let mut obligation = commitment.fulfill(proof_for(&commitment)).unwrap();
+        let settlement = settlement_for(Uuid::new_v4());
+
+        assert_eq!(
+            obligation.settle(&settlement),
+            Err(SettleError::CommitmentMismatch {
+                settlement_id: settlement.id,
+                commitment_id: commitment.id,
+            })
+        );
+        assert_eq!(obligation.status, ObligationStatus::Fulfilled);
+        assert!(!obligation.closed);
+    }
+}
@@ -0,0 +1,303 @@
+use std::{
+    fmt,
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+const ISO_4217_CURRENCY_CODES: [&str; 155] = [
+    "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD", "BDT",
+    "BGN", "BHD", "BIF", "BMD", "BND", "BOB", "BRL", "BSD", "BTN", "BWP", "BYN", "BZD", "CAD",
+    "CDF", "CHF", "CLP", "CNY", "COP", "CRC", "CUP", "CVE", "CZK", "DJF", "DKK", "DOP", "DZD",
+    "EGP", "ERN", "ETB", "EUR", "FJD", "FKP", "GBP", "GEL", "GHS", "GIP", "GMD", "GNF", "GTQ",
+    "GYD", "HKD", "HNL", "HTG", "HUF", "IDR", "ILS", "INR", "IQD", "IRR", "ISK", "JMD", "JOD",
+    "JPY", "KES", "KGS", "KHR", "KMF", "KPW", "KRW", "KWD", "KYD", "KZT", "LAK", "LBP", "LKR",
+    "LRD", "LSL", "LYD", "MAD", "MDL", "MGA", "MKD", "MMK", "MNT", "MOP", "MRU", "MUR", "MVR",
+    "MWK", "MXN", "MYR", "MZN", "NAD", "NGN", "NIO", "NOK", "NPR", "NZD", "OMR", "PAB", "PEN",
+    "PGK", "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD", "RUB", "RWF", "SAR", "SBD", "SCR",
+    "SDG", "SEK", "SGD", "SHP", "SLE", "SOS", "SRD", "SSP", "STN", "SVC", "SYP", "SZL", "THB",
+    "TJS", "TMT", "TND", "TOP", "TRY", "TTD", "TWD", "TZS", "UAH", "UGX", "USD", "UYU", "UZS",
+    "VES", "VND", "VUV", "WST", "XAF", "XCD", "XOF", "XPF", "YER", "ZAR", "ZMW", "ZWG",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MoneyError {
+    #[error("currency must be a 3-letter ISO code, got {0:?}")]
+    InvalidCurrency(String),
+    #[error("currency {0} is not a recognized ISO 4217 code")]
+    UnknownCurrency(String),
+    #[error("cannot combine {left} and {right} amounts")]
+    CurrencyMismatch { left: Currency, right: Currency },
+    #[error("{currency} amount overflowed")]
+    Overflow { currency: Currency },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "String", into = "String")]
+pub struct Currency(String);
+
+impl Currency {
+    pub fn new(code: &str) -> Result<Self, MoneyError> {
+        let normalized = code.trim().to_ascii_uppercase();
+        if normalized.len() != 3 || !normalized.chars().all(|ch| ch.is_ascii_alphabetic()) {
+            return Err(MoneyError::InvalidCurrency(code.to_string()));
+        }
+        if ISO_4217_CURRENCY_CODES
+            .binary_search(&normalized.as_str())
+            .is_err()
+        {
+            return Err(MoneyError::UnknownCurrency(normalized));
+        }
+        Ok(Self(normalized))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Currency {
+    type Err = MoneyError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::new(value)
+    }
+}
+
+impl TryFrom<String> for Currency {
+    type Error = MoneyError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(&value)
+    }
+}
+
+impl From<Currency> for String {
+    fn from(value: Currency) -> Self {
+        value.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Money {
+    pub amount: Decimal,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount: Decimal, currency: Currency) -> Self {
+        Self { amount, currency }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(Decimal::ZERO, currency)
+    }
+
+    pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.ensure_same_currency(other)?;
+        let amount = self
+            .amount
+            .checked_add(other.amount)
+            .ok_or_else(|| self.overflow())?;
+        Ok(Money::new(amount, self.currency.clone()))
+    }
+
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.ensure_same_currency(other)?;
+        let amount = self
+            .amount
+            .checked_sub(other.amount)
+            .ok_or_else(|| self.overflow())?;
+        Ok(Money::new(amount, self.currency.clone()))
+    }
+
+    pub fn sum<I>(currency: Currency, items: I) -> Result<Money, MoneyError>
+    where
+        I: IntoIterator<Item = Money>,
+    {
+        items
+            .into_iter()
+            .try_fold(Money::zero(currency), |total, item| total + item)
+    }
+
+    pub fn round_dp(self, dp: u32) -> Money {
+        Money::new(self.amount.round_dp(dp), self.currency)
+    }
+
+    fn ensure_same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency != other.currency {
+            return Err(MoneyError::CurrencyMismatch {
+                left: self.currency.clone(),
+                right: other.currency.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn overflow(&self) -> MoneyError {
+        MoneyError::Overflow {
+            currency: self.currency.clone(),
+        }
+    }
+}
+
+impl Add for Money {
+    type Output = Result<Money, MoneyError>;
+
+    fn add(self, other: Money) -> Self::Output {
+        self.checked_add(&other)
+    }
+}
+
+impl Sub for Money {
+    type Output = Result<Money, MoneyError>;
+
+    fn sub(self, other: Money) -> Self::Output {
+        self.checked_sub(&other)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usd(amount: Decimal) -> Money {
+        Money::new(amount, Currency::new("USD").unwrap())
+    }
+
+    fn eur(amount: Decimal) -> Money {
+        Money::new(amount, Currency::new("EUR").unwrap())
+    }
+
+    #[test]
+    fn iso_codes_are_sorted_and_unique_for_binary_search() {
+        assert!(
+            ISO_4217_CURRENCY_CODES
+                .windows(2)
+                .all(|pair| pair[0] < pair[1])
+        );
+        assert!(
+            ISO_4217_CURRENCY_CODES
+                .iter()
+                .all(|code| { code.len() == 3 && code.chars().all(|ch| ch.is_ascii_uppercase()) })
+        );
+    }
+
+    #[test]
+    fn every_listed_code_parses() {
+        for code in ISO_4217_CURRENCY_CODES {
+            assert_eq!(Currency::new(code).unwrap().as_str(), code);
+        }
+    }
+
+    #[test]
+    fn currency_parsing_normalizes_case_and_whitespace() {
+        assert_eq!(Currency::new("usd").unwrap().as_str(), "USD");
+        assert_eq!(" kes ".parse::<Currency>().unwrap().as_str(), "KES");
+        assert_eq!(
+            serde_json::from_str::<Currency>("\"eur\"").unwrap(),
+            Currency::new("EUR").unwrap()
+        );
+    }
+
+    #[test]
+    fn currency_parsing_rejects_malformed_and_unknown_codes() {
+        assert_eq!(
+            Currency::new("US"),
+            Err(MoneyError::InvalidCurrency("US".to_string()))
+        );
+        assert_eq!(
+            Currency::new("US1"),
+            Err(MoneyError::InvalidCurrency("US1".to_string()))
+        );
+        assert_eq!(
+            Currency::new("xyz"),
+            Err(MoneyError::UnknownCurrency("XYZ".to_string()))
+        );
+        assert!(serde_json::from_str::<Currency>("\"ZZZ\"").is_err());
+    }
+
+    #[test]
+    fn same_currency_add_and_sub() {
+        let total = usd(Decimal::new(1050, 2))
+            .checked_add(&usd(Decimal::new(250, 2)))
+            .unwrap();
+        assert_eq!(total, usd(Decimal::new(1300, 2)));
+
+        let difference = (usd(Decimal::new(1000, 2)) - usd(Decimal::new(1250, 2))).unwrap();
+        assert_eq!(difference, usd(Decimal::new(-250, 2)));
+    }
+
+    #[test]
+    fn mixed_currency_add_and_sub_error() {
+        let expected = MoneyError::CurrencyMismatch {
+            left: Currency::new("USD").unwrap(),
+            right: Currency::new("EUR").unwrap(),
+        };
+        assert_eq!(
+            usd(Decimal::ONE).checked_add(&eur(Decimal::ONE)),
+            Err(expected.clone())
+        );
+        assert_eq!(
+            usd(Decimal::ONE).checked_sub(&eur(Decimal::ONE)),
+            Err(expected)
+        );
+    }
+
+    #[test]
+    fn add_and_sub_report_overflow_instead_of_panicking() {
+        let overflow = MoneyError::Overflow {
+            currency: Currency::new("USD").unwrap(),
+        };
+        assert_eq!(
+            usd(Decimal::MAX).checked_add(&usd(Decimal::ONE)),
+            Err(overflow.clone())
+        );
+        assert_eq!(
+            usd(Decimal::MIN).checked_sub(&usd(Decimal::ONE)),
+            Err(overflow)
+        );
+    }
+
+    #[test]
+    fn sum_folds_in_the_given_currency() {
+        let total = Money::sum(
+            Currency::new("USD").unwrap(),
+            [usd(Decimal::ONE), usd(Decimal::TWO)],
+        )
+        .unwrap();
+        assert_eq!(total, usd(Decimal::new(3, 0)));
+
+        assert_eq!(
+            Money::sum(Currency::new("USD").unwrap(), []).unwrap(),
+            usd(Decimal::ZERO)
+        );
+        assert!(matches!(
+            Money::sum(Currency::new("USD").unwrap(), [eur(Decimal::ONE)]),
+            Err(MoneyError::CurrencyMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn round_dp_keeps_currency() {
+        assert_eq!(
+            usd(Decimal::new(123456, 5)).round_dp(2),
+            usd(Decimal::new(123, 2))
+        );
+    }
+}
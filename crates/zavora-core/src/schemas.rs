@@ -0,0 +1,153 @@
+use schemars::schema_for;
+use serde_json::{Value, json};
+
+use crate::models::{Commitment, Obligation, Proof, Settlement};
+
+pub const SCHEMA_VERSION: &str = "1.0.0";
+
+pub fn schemas() -> Value {
+    json!({
+        "version": SCHEMA_VERSION,
+        "schemas": {
+            "Commitment": schema_for!(Commitment),
+            "Obligation": schema_for!(Obligation),
+            "Proof": schema_for!(Proof),
+            "Settlement": schema_for!(Settlement),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use serde::{Serialize, de::DeserializeOwned};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::models::{CommitmentStatus, ObligationStatus};
+
+    fn required(name: &str) -> Vec<String> {
+        let mut required: Vec<String> =
+            serde_json::from_value(schemas()["schemas"][name]["required"].clone())
+                .unwrap_or_else(|err| panic!("{name} has no required list: {err}"));
+        required.sort();
+        required
+    }
+
+    fn assert_round_trips<T: Serialize + DeserializeOwned>(name: &str, sample: &T) {
+        let encoded = serde_json::to_value(sample).unwrap();
+        let properties = schemas()["schemas"][name]["properties"].clone();
+        for key in encoded.as_object().unwrap().keys() {
+            assert!(
+                properties.get(key).is_some(),
+                "{name} schema has no property {key}"
+            );
+        }
+        let decoded: T = serde_json::from_value(encoded.clone()).unwrap();
+        assert_eq!(serde_json::to_value(decoded).unwrap(), encoded);
+    }
+
+    #[test]
+    fn schemas_carry_the_published_version_and_every_exported_type() {
+        let document = schemas();
+        assert_eq!(document["version"], SCHEMA_VERSION);
+        assert_eq!(SCHEMA_VERSION, "1.0.0");
+        let mut names: Vec<&String> = document["schemas"].as_object().unwrap().keys().collect();
+        names.sort();
+        assert_eq!(names, ["Commitment", "Obligation", "Proof", "Settlement"]);
+    }
+
+    #[test]
+    fn schemas_require_every_field_without_a_serde_default() {
+        assert_eq!(
+            required("Commitment"),
+            [
+                "commitment_type",
+                "created_at",
+                "from_party",
+                "id",
+                "risk_class",
+                "status",
+                "terms",
+                "to_party",
+            ]
+        );
+        assert_eq!(
+            required("Obligation"),
+            [
+                "closed",
+                "commitment_id",
+                "depends_on",
+                "due_at",
+                "id",
+                "owner"
+            ]
+        );
+        assert_eq!(
+            required("Proof"),
+            ["created_at", "id", "linked_id", "payload_ref", "source"]
+        );
+        assert_eq!(
+            required("Settlement"),
+            [
+                "amount",
+                "commitment_id",
+                "created_at",
+                "currency",
+                "id",
+                "status",
+            ]
+        );
+    }
+
+    #[test]
+    fn sample_values_round_trip_through_their_schema_shape() {
+        let commitment = Commitment {
+            id: Uuid::new_v4(),
+            commitment_type: "SERVICE_DELIVERY".to_string(),
+            from_party: "zavora".to_string(),
+            to_party: "customer-1".to_string(),
+            terms: "deliver onboarding workshop".to_string(),
+            risk_class: "LOW".to_string(),
+            status: CommitmentStatus::Active,
+            created_at: Utc::now(),
+        };
+        assert_round_trips("Commitment", &commitment);
+        assert_round_trips(
+            "Obligation",
+            &Obligation {
+                id: Uuid::new_v4(),
+                commitment_id: commitment.id,
+                owner: "customer-1".to_string(),
+                due_at: Utc::now(),
+                depends_on: vec![Uuid::new_v4()],
+                closed: false,
+                status: ObligationStatus::Fulfilled,
+                proof_id: Some(Uuid::new_v4()),
+                settlement_id: None,
+            },
+        );
+        assert_round_trips(
+            "Proof",
+            &Proof {
+                id: Uuid::new_v4(),
+                linked_id: commitment.id,
+                source: "ops-agent".to_string(),
+                payload_ref: "s3://proofs/workshop.pdf".to_string(),
+                created_at: Utc::now(),
+            },
+        );
+        assert_round_trips(
+            "Settlement",
+            &Settlement {
+                id: Uuid::new_v4(),
+                commitment_id: commitment.id,
+                amount: Decimal::new(150_000, 2),
+                currency: "USD".to_string(),
+                status: "COMPLETED".to_string(),
+                created_at: Utc::now(),
+            },
+        );
+    }
+}
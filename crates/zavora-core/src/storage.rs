@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use serde_json::Value;
 use uuid::Uuid;
 
 use crate::events::DomainEvent;
@@ -8,6 +9,7 @@ use crate::events::DomainEvent;
 pub struct EventEnvelope {
     pub sequence: i64,
     pub stream_id: Uuid,
+    pub schema_version: u32,
     pub event: DomainEvent,
     pub stored_at: DateTime<Utc>,
 }
@@ -15,7 +17,37 @@ pub struct EventEnvelope {
 #[async_trait]
 pub trait EventStore: Send + Sync {
     async fn append(&self, stream_id: Uuid, event: DomainEvent) -> anyhow::Result<EventEnvelope>;
+
+    async fn append_batch(
+        &self,
+        stream_id: Uuid,
+        events: Vec<DomainEvent>,
+    ) -> anyhow::Result<Vec<EventEnvelope>> {
+        let mut envelopes = Vec::with_capacity(events.len());
+        for event in events {
+            envelopes.push(self.append(stream_id, event).await?);
+        }
+        Ok(envelopes)
+    }
+
     async fn stream(&self, stream_id: Uuid) -> anyhow::Result<Vec<EventEnvelope>>;
+
+    async fn stream_after(
+        &self,
+        stream_id: Uuid,
+        after_sequence: i64,
+    ) -> anyhow::Result<Vec<EventEnvelope>> {
+        let mut envelopes = self.stream(stream_id).await?;
+        envelopes.retain(|envelope| envelope.sequence > after_sequence);
+        Ok(envelopes)
+    }
+}
+
+#[async_trait]
+pub trait SnapshotStore: Send + Sync {
+    async fn save(&self, stream_id: Uuid, version: i64, state_json: Value) -> anyhow::Result<()>;
+
+    async fn load(&self, stream_id: Uuid) -> anyhow::Result<Option<(i64, Value)>>;
 }
 
 #[async_trait]
@@ -2,38 +2,114 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 use chrono::Utc;
+use serde_json::Value;
 use tokio::sync::RwLock;
 use uuid::Uuid;
-use zavora_core::{DomainEvent, EventEnvelope, EventStore, ProjectionStore};
+use zavora_core::{
+    DOMAIN_EVENT_SCHEMA_VERSION, DomainEvent, EventEnvelope, EventStore, ProjectionStore,
+    SnapshotStore,
+};
 
 #[derive(Default)]
 pub struct InMemoryEventStore {
-    streams: RwLock<HashMap<Uuid, Vec<EventEnvelope>>>,
-    sequence: RwLock<i64>,
+    state: RwLock<StoreState>,
+}
+
+#[derive(Default)]
+struct StoreState {
+    streams: HashMap<Uuid, Vec<EventEnvelope>>,
+    sequence: i64,
 }
 
 #[async_trait]
 impl EventStore for InMemoryEventStore {
     async fn append(&self, stream_id: Uuid, event: DomainEvent) -> anyhow::Result<EventEnvelope> {
-        let mut sequence_guard = self.sequence.write().await;
-        *sequence_guard += 1;
-
-        let envelope = EventEnvelope {
-            sequence: *sequence_guard,
-            stream_id,
-            event,
-            stored_at: Utc::now(),
-        };
+        let mut envelopes = self.append_batch(stream_id, vec![event]).await?;
+        envelopes
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("append produced no envelope"))
+    }
+
+    async fn append_batch(
+        &self,
+        stream_id: Uuid,
+        events: Vec<DomainEvent>,
+    ) -> anyhow::Result<Vec<EventEnvelope>> {
+        if events.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let stored_at = Utc::now();
+        let mut state = self.state.write().await;
+        let first_sequence = state.sequence + 1;
+        state.sequence += events.len() as i64;
 
-        let mut streams = self.streams.write().await;
-        streams.entry(stream_id).or_default().push(envelope.clone());
+        let envelopes: Vec<EventEnvelope> = events
+            .into_iter()
+            .zip(first_sequence..)
+            .map(|(event, sequence)| EventEnvelope {
+                sequence,
+                stream_id,
+                schema_version: DOMAIN_EVENT_SCHEMA_VERSION,
+                event,
+                stored_at,
+            })
+            .collect();
 
-        Ok(envelope)
+        state
+            .streams
+            .entry(stream_id)
+            .or_default()
+            .extend(envelopes.iter().cloned());
+
+        Ok(envelopes)
     }
 
     async fn stream(&self, stream_id: Uuid) -> anyhow::Result<Vec<EventEnvelope>> {
-        let streams = self.streams.read().await;
-        Ok(streams.get(&stream_id).cloned().unwrap_or_default())
+        let state = self.state.read().await;
+        Ok(state.streams.get(&stream_id).cloned().unwrap_or_default())
+    }
+
+    async fn stream_after(
+        &self,
+        stream_id: Uuid,
+        after_sequence: i64,
+    ) -> anyhow::Result<Vec<EventEnvelope>> {
+        let state = self.state.read().await;
+        Ok(state
+            .streams
+            .get(&stream_id)
+            .map(|envelopes| {
+                envelopes
+                    .iter()
+                    .filter(|envelope| envelope.sequence > after_sequence)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
+#[derive(Default)]
+pub struct InMemorySnapshotStore {
+    snapshots: RwLock<HashMap<Uuid, (i64, Value)>>,
+}
+
+#[async_trait]
+impl SnapshotStore for InMemorySnapshotStore {
+    async fn save(&self, stream_id: Uuid, version: i64, state_json: Value) -> anyhow::Result<()> {
+        let mut snapshots = self.snapshots.write().await;
+        let is_newer = snapshots
+            .get(&stream_id)
+            .is_none_or(|(current, _)| version >= *current);
+        if is_newer {
+            snapshots.insert(stream_id, (version, state_json));
+        }
+        Ok(())
+    }
+
+    async fn load(&self, stream_id: Uuid) -> anyhow::Result<Option<(i64, Value)>> {
+        Ok(self.snapshots.read().await.get(&stream_id).cloned())
     }
 }
 
@@ -46,3 +122,207 @@ impl ProjectionStore for NoopProjectionStore {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zavora_core::DomainEventKind;
+
+    use super::*;
+
+    fn stock_event(stream_id: Uuid, kind: DomainEventKind, quantity: i64) -> DomainEvent {
+        DomainEvent {
+            id: Uuid::new_v4(),
+            aggregate_id: stream_id,
+            kind,
+            occurred_at: Utc::now(),
+            payload: json!({ "quantity": quantity }),
+        }
+    }
+
+    fn apply(mut state: Value, envelope: &EventEnvelope) -> Value {
+        let quantity = envelope.event.payload["quantity"].as_i64().unwrap();
+        let delta = match envelope.event.kind {
+            DomainEventKind::StockReceived => quantity,
+            DomainEventKind::StockIssued => -quantity,
+            _ => 0,
+        };
+        state["on_hand"] = json!(state["on_hand"].as_i64().unwrap() + delta);
+        state["movements"] = json!(state["movements"].as_i64().unwrap() + 1);
+        state
+    }
+
+    fn initial_state() -> Value {
+        json!({ "on_hand": 0, "movements": 0 })
+    }
+
+    #[tokio::test]
+    async fn snapshot_plus_tail_matches_a_full_replay() {
+        let events = InMemoryEventStore::default();
+        let snapshots = InMemorySnapshotStore::default();
+        let stream_id = Uuid::new_v4();
+        let other_stream = Uuid::new_v4();
+
+        let head = events
+            .append_batch(
+                stream_id,
+                vec![
+                    stock_event(stream_id, DomainEventKind::StockReceived, 10),
+                    stock_event(stream_id, DomainEventKind::StockIssued, 3),
+                ],
+            )
+            .await
+            .unwrap();
+        let snapshot = head.iter().fold(initial_state(), apply);
+        let snapshot_version = head.last().unwrap().sequence;
+        snapshots
+            .save(stream_id, snapshot_version, snapshot)
+            .await
+            .unwrap();
+
+        events
+            .append(
+                other_stream,
+                stock_event(other_stream, DomainEventKind::StockReceived, 99),
+            )
+            .await
+            .unwrap();
+        events
+            .append_batch(
+                stream_id,
+                vec![
+                    stock_event(stream_id, DomainEventKind::StockReceived, 5),
+                    stock_event(stream_id, DomainEventKind::StockIssued, 4),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let full_replay = events
+            .stream(stream_id)
+            .await
+            .unwrap()
+            .iter()
+            .fold(initial_state(), apply);
+
+        let (version, state) = snapshots.load(stream_id).await.unwrap().unwrap();
+        let tail = events.stream_after(stream_id, version).await.unwrap();
+        assert_eq!(tail.len(), 2);
+        let from_snapshot = tail.iter().fold(state, apply);
+
+        assert_eq!(from_snapshot, full_replay);
+        assert_eq!(full_replay, json!({ "on_hand": 8, "movements": 4 }));
+    }
+
+    #[tokio::test]
+    async fn an_older_snapshot_does_not_replace_a_newer_one() {
+        let snapshots = InMemorySnapshotStore::default();
+        let stream_id = Uuid::new_v4();
+
+        snapshots
+            .save(stream_id, 5, json!({ "v": 5 }))
+            .await
+            .unwrap();
+        snapshots
+            .save(stream_id, 3, json!({ "v": 3 }))
+            .await
+            .unwrap();
+        assert_eq!(
+            snapshots.load(stream_id).await.unwrap(),
+            Some((5, json!({ "v": 5 })))
+        );
+        assert_eq!(snapshots.load(Uuid::new_v4()).await.unwrap(), None);
+    }
+
+    fn numbered_events(stream_id: Uuid, count: i64) -> Vec<DomainEvent> {
+        (0..count)
+            .map(|index| stock_event(stream_id, DomainEventKind::StockReceived, index))
+            .collect()
+    }
+
+    fn quantities(envelopes: &[EventEnvelope]) -> Vec<i64> {
+        envelopes
+            .iter()
+            .map(|envelope| envelope.event.payload["quantity"].as_i64().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn append_batch_keeps_order_and_assigns_a_contiguous_range() {
+        let events = InMemoryEventStore::default();
+        let stream_id = Uuid::new_v4();
+
+        let first = events
+            .append(
+                stream_id,
+                stock_event(stream_id, DomainEventKind::StockReceived, 99),
+            )
+            .await
+            .unwrap();
+        let batch = events
+            .append_batch(stream_id, numbered_events(stream_id, 100))
+            .await
+            .unwrap();
+
+        assert_eq!(first.sequence, 1);
+        let sequences: Vec<i64> = batch.iter().map(|envelope| envelope.sequence).collect();
+        assert_eq!(sequences, (2..=101).collect::<Vec<_>>());
+        assert_eq!(quantities(&batch), (0..100).collect::<Vec<_>>());
+        assert!(batch.iter().all(|envelope| envelope.stream_id == stream_id));
+
+        let stream = events.stream(stream_id).await.unwrap();
+        assert_eq!(stream.len(), 101);
+        assert_eq!(quantities(&stream[1..]), (0..100).collect::<Vec<_>>());
+        assert!(
+            events
+                .append_batch(stream_id, Vec::new())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_batches_never_interleave_or_reuse_sequences() {
+        let events = std::sync::Arc::new(InMemoryEventStore::default());
+        let shared_stream = Uuid::new_v4();
+
+        let writers: Vec<_> = (0..8)
+            .map(|_| {
+                let events = events.clone();
+                tokio::spawn(async move {
+                    let mut batches = Vec::new();
+                    for _ in 0..10 {
+                        batches.push(
+                            events
+                                .append_batch(shared_stream, numbered_events(shared_stream, 25))
+                                .await
+                                .unwrap(),
+                        );
+                    }
+                    batches
+                })
+            })
+            .collect();
+
+        let mut all_sequences = Vec::new();
+        for writer in writers {
+            for batch in writer.await.unwrap() {
+                let sequences: Vec<i64> = batch.iter().map(|envelope| envelope.sequence).collect();
+                let start = sequences[0];
+                assert_eq!(sequences, (start..start + 25).collect::<Vec<_>>());
+                assert_eq!(quantities(&batch), (0..25).collect::<Vec<_>>());
+                all_sequences.extend(sequences);
+            }
+        }
+        all_sequences.sort_unstable();
+        assert_eq!(all_sequences, (1..=2000).collect::<Vec<_>>());
+
+        let stream = events.stream(shared_stream).await.unwrap();
+        let stored: Vec<i64> = stream.iter().map(|envelope| envelope.sequence).collect();
+        assert_eq!(stored, (1..=2000).collect::<Vec<_>>());
+        for chunk in stream.chunks(25) {
+            assert_eq!(quantities(chunk), (0..25).collect::<Vec<_>>());
+        }
+    }
+}
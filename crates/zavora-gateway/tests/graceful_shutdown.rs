@@ -0,0 +1,90 @@
+use std::{process::Stdio, time::Duration};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    process::Command,
+};
+use zavora_platform::testing::TestDatabase;
+
+const SHUTDOWN_TIMEOUT_SECS: u64 = 5;
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("free port")
+        .port()
+}
+
+async fn get_status_line(addr: &str, path: &str) -> Option<String> {
+    let mut stream = TcpStream::connect(addr).await.ok()?;
+    stream
+        .write_all(
+            format!("GET {path} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n").as_bytes(),
+        )
+        .await
+        .ok()?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.ok()?;
+    response.lines().next().map(str::to_string)
+}
+
+#[tokio::test]
+async fn sigterm_drains_and_exits_cleanly_within_the_shutdown_timeout() {
+    let Some(db) = TestDatabase::create().await else {
+        return;
+    };
+    let addr = format!("127.0.0.1:{}", free_port());
+    let gateway = Command::new(env!("CARGO_BIN_EXE_zavora-gateway"))
+        .env("DATABASE_URL", db.url())
+        .env("REDIS_URL", "redis://127.0.0.1:1/")
+        .env("AGENT_TOKEN_SECRET", "graceful-shutdown-test-secret")
+        .env("SKILL_EXECUTOR_COMMAND", "/bin/cat")
+        .env("HTTP_ADDR", &addr)
+        .env("SHUTDOWN_TIMEOUT_SECS", SHUTDOWN_TIMEOUT_SECS.to_string())
+        .env("NO_COLOR", "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .expect("spawn gateway");
+
+    let mut ready = false;
+    for _ in 0..150 {
+        if get_status_line(&addr, "/livez")
+            .await
+            .is_some_and(|status| status.contains(" 200 "))
+        {
+            ready = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(ready, "gateway did not start listening on {addr}");
+
+    let pid = gateway.id().expect("gateway pid").to_string();
+    let killed = Command::new("kill")
+        .args(["-TERM", &pid])
+        .status()
+        .await
+        .expect("send SIGTERM");
+    assert!(killed.success());
+
+    let output = tokio::time::timeout(
+        Duration::from_secs(SHUTDOWN_TIMEOUT_SECS + 5),
+        gateway.wait_with_output(),
+    )
+    .await
+    .expect("gateway exits within the shutdown timeout")
+    .expect("gateway output");
+    let logs = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(output.status.success(), "exit {}: {logs}", output.status);
+    assert!(logs.contains("shutdown complete"), "{logs}");
+    assert!(get_status_line(&addr, "/livez").await.is_none());
+
+    db.drop().await;
+}
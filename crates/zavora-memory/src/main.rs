@@ -14,26 +14,14 @@ use sqlx::{PgPool, Row};
 use tracing::{error, info};
 use uuid::Uuid;
 use zavora_platform::{
-    MemorySearchHit, MemorySearchRequest, MemorySearchResponse, MemoryWriteRequest,
+    AgentRegistry, MemorySearchHit, MemorySearchRequest, MemorySearchResponse, MemoryWriteRequest,
     MemoryWriteResponse, ServiceConfig, connect_database,
 };
 
-const REGISTERED_AGENT_IDS: [&str; 10] = [
-    "strategy-agent",
-    "sales-agent",
-    "procurement-agent",
-    "warehouse-agent",
-    "ar-agent",
-    "controller-agent",
-    "board-agent",
-    "ops-orchestrator-agent",
-    "audit-agent",
-    "payroll-agent",
-];
-
 #[derive(Clone)]
 struct AppState {
     pool: PgPool,
+    agent_registry: AgentRegistry,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -80,11 +68,16 @@ async fn main() -> AnyResult<()> {
         .init();
 
     let config = ServiceConfig::from_env("0.0.0.0:8100")?;
-    let pool = connect_database(&config.database_url).await?;
+    let pool = connect_database(&config).await?;
 
-    let state = AppState { pool };
+    let agent_registry = AgentRegistry::load(&pool).await?;
+    let state = AppState {
+        pool,
+        agent_registry,
+    };
     let router = Router::new()
         .route("/healthz", get(healthz))
+        .route("/livez", get(livez))
         .route("/memory/entries", post(write_memory))
         .route("/memory/search", post(search_memory))
         .route("/memory/retention/run", post(run_retention))
@@ -99,7 +92,38 @@ async fn main() -> AnyResult<()> {
     Ok(())
 }
 
-async fn healthz() -> &'static str {
+async fn healthz(State(state): State<AppState>) -> (StatusCode, Json<Value>) {
+    let database = tokio::time::timeout(
+        std::time::Duration::from_millis(500),
+        sqlx::query_scalar::<_, i32>("SELECT 1").fetch_one(&state.pool),
+    )
+    .await;
+
+    match database {
+        Ok(Ok(_)) => (
+            StatusCode::OK,
+            Json(json!({ "status": "ok", "failed_dependencies": [] })),
+        ),
+        Ok(Err(err)) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "status": "down",
+                "failed_dependencies": ["database"],
+                "error": err.to_string(),
+            })),
+        ),
+        Err(_) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "status": "down",
+                "failed_dependencies": ["database"],
+                "error": "check exceeded 500ms",
+            })),
+        ),
+    }
+}
+
+async fn livez() -> &'static str {
     "ok"
 }
 
@@ -166,7 +190,7 @@ async fn write_memory_inner(
     state: &AppState,
     payload: MemoryWriteRequest,
 ) -> Result<MemoryWriteResponse, (StatusCode, String)> {
-    validate_write_request(&payload).map_err(invalid_request)?;
+    validate_write_request(&state.agent_registry, &payload).map_err(invalid_request)?;
 
     let memory_id = Uuid::new_v4();
     let now = Utc::now();
@@ -224,7 +248,7 @@ async fn search_memory_inner(
     state: &AppState,
     payload: MemorySearchRequest,
 ) -> Result<MemorySearchResponse, (StatusCode, String)> {
-    validate_search_request(&payload).map_err(invalid_request)?;
+    validate_search_request(&state.agent_registry, &payload).map_err(invalid_request)?;
     let limit = payload.limit.unwrap_or(10).clamp(1, 50);
 
     let rows = sqlx::query(
@@ -244,6 +268,7 @@ async fn search_memory_inner(
             ) AS score
         FROM agent_semantic_memory
         WHERE agent_name = $1
+          AND tombstoned_at IS NULL
           AND ($3::text IS NULL OR scope = $3)
           AND ($4::uuid IS NULL OR entity_id = $4)
           AND (
@@ -354,7 +379,7 @@ async fn run_retention_inner(
     payload: RunRetentionRequest,
 ) -> Result<RunRetentionResponse, (StatusCode, String)> {
     let actor = payload.requested_by_agent_id.trim();
-    validate_registered_agent(actor).map_err(invalid_request)?;
+    validate_registered_agent(&state.agent_registry, actor).map_err(invalid_request)?;
 
     let run_at = Utc::now();
     let dry_run = payload.dry_run.unwrap_or(false);
@@ -461,6 +486,7 @@ async fn run_retention_inner(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn insert_memory_provenance(
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     memory_id: Option<Uuid>,
@@ -493,7 +519,7 @@ async fn insert_memory_provenance(
     Ok(())
 }
 
-fn validate_write_request(payload: &MemoryWriteRequest) -> AnyResult<()> {
+fn validate_write_request(registry: &AgentRegistry, payload: &MemoryWriteRequest) -> AnyResult<()> {
     if payload.agent_name.trim().is_empty() {
         anyhow::bail!("agent_name is required");
     }
@@ -510,13 +536,16 @@ fn validate_write_request(payload: &MemoryWriteRequest) -> AnyResult<()> {
         anyhow::bail!("source_ref is required");
     }
 
-    validate_registered_agent(payload.agent_name.trim())?;
-    validate_registered_agent(payload.actor_agent_id.trim())?;
+    validate_registered_agent(registry, payload.agent_name.trim())?;
+    validate_registered_agent(registry, payload.actor_agent_id.trim())?;
 
     Ok(())
 }
 
-fn validate_search_request(payload: &MemorySearchRequest) -> AnyResult<()> {
+fn validate_search_request(
+    registry: &AgentRegistry,
+    payload: &MemorySearchRequest,
+) -> AnyResult<()> {
     if payload.agent_name.trim().is_empty() {
         anyhow::bail!("agent_name is required");
     }
@@ -527,19 +556,19 @@ fn validate_search_request(payload: &MemorySearchRequest) -> AnyResult<()> {
         anyhow::bail!("query is required");
     }
 
-    validate_registered_agent(payload.agent_name.trim())?;
-    validate_registered_agent(payload.requested_by_agent_id.trim())?;
+    validate_registered_agent(registry, payload.agent_name.trim())?;
+    validate_registered_agent(registry, payload.requested_by_agent_id.trim())?;
 
     Ok(())
 }
 
-fn validate_registered_agent(agent_id: &str) -> AnyResult<()> {
-    if REGISTERED_AGENT_IDS.contains(&agent_id) {
+fn validate_registered_agent(registry: &AgentRegistry, agent_id: &str) -> AnyResult<()> {
+    if registry.is_registered(agent_id) {
         return Ok(());
     }
 
     anyhow::bail!(
-        "unknown agent_id '{}'; register it in docs/agents.md first",
+        "unknown agent_id '{}'; register it in agent_registry first",
         agent_id
     )
 }
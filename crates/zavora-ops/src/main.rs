@@ -1,16 +1,17 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
 use futures_util::StreamExt;
-use redis::Msg;
 use rust_decimal::Decimal;
 use serde_json::{Value, json};
 use sha2::{Digest, Sha256};
 use sqlx::{PgPool, Row};
 use std::{error::Error as StdError, fmt};
-use tracing::{error, info};
+use tracing::{Instrument, error, info, info_span};
 use uuid::Uuid;
+use zavora_core::{DomainEventKind, OrderFailedPayload, OrderFulfilledPayload};
 use zavora_platform::{
-    OrderCreatedEvent, OrderFulfilledEvent, RedisBus, ServiceConfig, connect_database,
+    ESCALATION_CREATED, OrderCreatedEvent, OrderFulfilledEvent, RedisBus, ServiceConfig,
+    append_aggregate_event, connect_database, enqueue_escalation_event,
 };
 
 const AR_ACCOUNT: &str = "1100";
@@ -110,32 +111,45 @@ async fn main() -> Result<()> {
         .init();
 
     let config = ServiceConfig::worker_from_env()?;
-    let pool = connect_database(&config.database_url).await?;
+    let pool = connect_database(&config).await?;
     let redis = RedisBus::connect(&config.redis_url)?;
 
-    let mut pubsub = redis.client().get_async_pubsub().await?;
-    pubsub.subscribe("orders.created").await?;
-    let mut messages = pubsub.on_message();
+    let mut events = Box::pin(futures_util::stream::select(
+        redis.subscribe_json::<OrderCreatedEvent>("orders.created"),
+        redis.subscribe_json::<OrderCreatedEvent>("orders.fulfill"),
+    ));
 
-    info!("ops worker subscribed to orders.created");
+    info!("ops worker listening on orders.created and orders.fulfill");
 
     loop {
-        let msg = messages
+        let event = events
             .next()
             .await
-            .context("orders.created stream ended unexpectedly")?;
-        if let Err(err) = handle_message(&pool, &redis, msg).await {
+            .context("order dispatch stream ended unexpectedly")?;
+        let result = match event {
+            Ok(event) => {
+                let span = info_span!(
+                    "order.dispatch",
+                    order_id = %event.order_id,
+                    correlation_id = event.correlation_id.as_deref().unwrap_or("-"),
+                );
+                handle_message(&pool, &redis, event).instrument(span).await
+            }
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
             error!("failed to process message: {err:#}");
         }
     }
 }
 
-async fn handle_message(pool: &PgPool, redis: &RedisBus, msg: Msg) -> Result<()> {
-    let payload: String = msg.get_payload()?;
-    let event: OrderCreatedEvent = serde_json::from_str(&payload)?;
-
+async fn handle_message(pool: &PgPool, redis: &RedisBus, event: OrderCreatedEvent) -> Result<()> {
     match process_order(pool, event.order_id).await {
-        Ok(done) => {
+        Ok(None) => {
+            info!("order {} is awaiting stock; skipping", event.order_id);
+            Ok(())
+        }
+        Ok(Some(done)) => {
             redis.publish_json("orders.fulfilled", &done).await?;
             info!("order {} fulfilled", done.order_id);
             Ok(())
@@ -148,7 +162,8 @@ async fn handle_message(pool: &PgPool, redis: &RedisBus, msg: Msg) -> Result<()>
     }
 }
 
-async fn process_order(pool: &PgPool, order_id: Uuid) -> Result<OrderFulfilledEvent> {
+/// Returns `None` for orders parked as `AWAITING_STOCK`; the warehouse agent releases them again.
+async fn process_order(pool: &PgPool, order_id: Uuid) -> Result<Option<OrderFulfilledEvent>> {
     let mut tx = pool.begin().await?;
 
     let order_row = sqlx::query(
@@ -174,11 +189,16 @@ async fn process_order(pool: &PgPool, order_id: Uuid) -> Result<OrderFulfilledEv
 
     if current_status == "FULFILLED" {
         tx.commit().await?;
-        return Ok(OrderFulfilledEvent {
+        return Ok(Some(OrderFulfilledEvent {
             order_id,
             settled_amount: (quantity * unit_price).round_dp(4),
             currency,
-        });
+        }));
+    }
+
+    if current_status == "AWAITING_STOCK" {
+        tx.commit().await?;
+        return Ok(None);
     }
 
     if current_status == "PENDING_APPROVAL" || current_status == "FROZEN" {
@@ -454,6 +474,20 @@ async fn process_order(pool: &PgPool, order_id: Uuid) -> Result<OrderFulfilledEv
     .execute(&mut *tx)
     .await?;
 
+    append_aggregate_event(
+        &mut *tx,
+        order_id,
+        DomainEventKind::OrderFulfilled,
+        issued_at,
+        &OrderFulfilledPayload {
+            order_id,
+            settled_amount: revenue,
+            currency: currency.clone(),
+            fulfilled_at: issued_at,
+        },
+    )
+    .await?;
+
     write_execution_memory(
         &mut tx,
         order_id,
@@ -467,11 +501,11 @@ async fn process_order(pool: &PgPool, order_id: Uuid) -> Result<OrderFulfilledEv
 
     tx.commit().await?;
 
-    Ok(OrderFulfilledEvent {
+    Ok(Some(OrderFulfilledEvent {
         order_id,
         settled_amount: revenue,
         currency,
-    })
+    }))
 }
 
 async fn ensure_inventory_for_order(
@@ -825,6 +859,7 @@ async fn invoke_skill(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn insert_skill_invocation(
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     context: &SkillExecutionContext,
@@ -930,6 +965,8 @@ async fn insert_skill_escalation(
     .execute(&mut **tx)
     .await?;
 
+    enqueue_escalation_event(tx, escalation_id, ESCALATION_CREATED).await?;
+
     Ok(escalation_id)
 }
 
@@ -1102,6 +1139,7 @@ async fn create_invoice(
     Ok(row.try_get("id")?)
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn post_ar_subledger_entry(
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     invoice_id: Uuid,
@@ -1206,6 +1244,7 @@ async fn update_invoice_status_from_balance(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn record_ap_obligation_with_entry(
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     order_id: Uuid,
@@ -1426,6 +1465,7 @@ async fn mark_ap_obligation_settled(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn post_ap_subledger_entry(
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     ap_obligation_id: Uuid,
@@ -1534,15 +1574,30 @@ async fn insert_journal(
 }
 
 async fn mark_order_failed(pool: &PgPool, order_id: Uuid, reason: &str) -> Result<()> {
+    let now = Utc::now();
+    let mut tx = pool.begin().await?;
     sqlx::query(
         "UPDATE orders SET status = 'FAILED', failure_reason = $2, updated_at = $3 WHERE id = $1",
     )
     .bind(order_id)
     .bind(reason)
-    .bind(Utc::now())
-    .execute(pool)
+    .bind(now)
+    .execute(&mut *tx)
     .await?;
 
+    append_aggregate_event(
+        &mut *tx,
+        order_id,
+        DomainEventKind::OrderFailed,
+        now,
+        &OrderFailedPayload {
+            order_id,
+            failure_reason: reason.to_string(),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
     Ok(())
 }
 
@@ -1749,6 +1804,7 @@ async fn write_failure_memory(pool: &PgPool, order_id: Uuid, reason: &str) -> Re
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn insert_memory_provenance(
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     memory_id: Option<Uuid>,
@@ -0,0 +1,96 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{Arc, RwLock},
+};
+
+use anyhow::Result;
+use serde::Serialize;
+use sqlx::{PgPool, Row};
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AgentRoster {
+    pub agent_ids: BTreeSet<String>,
+    pub permissions: BTreeMap<String, BTreeSet<String>>,
+}
+
+#[derive(Clone, Default)]
+pub struct AgentRegistry {
+    roster: Arc<RwLock<AgentRoster>>,
+}
+
+impl AgentRegistry {
+    pub async fn load(pool: &PgPool) -> Result<Self> {
+        let registry = Self::default();
+        registry.refresh(pool).await?;
+        Ok(registry)
+    }
+
+    pub fn from_roster(roster: AgentRoster) -> Self {
+        Self {
+            roster: Arc::new(RwLock::new(roster)),
+        }
+    }
+
+    pub async fn refresh(&self, pool: &PgPool) -> Result<AgentRoster> {
+        let rows = sqlx::query(
+            r#"
+            SELECT r.agent_id, p.capability
+            FROM agent_registry r
+            LEFT JOIN agent_permissions p ON p.agent_id = r.agent_id
+            WHERE r.active
+            ORDER BY r.agent_id, p.capability
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut roster = AgentRoster::default();
+        for row in rows {
+            let agent_id: String = row.try_get("agent_id")?;
+            let capability: Option<String> = row.try_get("capability")?;
+            let capabilities = roster.permissions.entry(agent_id.clone()).or_default();
+            if let Some(capability) = capability {
+                capabilities.insert(capability);
+            }
+            roster.agent_ids.insert(agent_id);
+        }
+
+        *self
+            .roster
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = roster.clone();
+        Ok(roster)
+    }
+
+    pub fn is_registered(&self, agent_id: &str) -> bool {
+        self.read(|roster| roster.agent_ids.contains(agent_id))
+    }
+
+    pub fn has_capability(&self, agent_id: &str, capability: &str) -> bool {
+        self.read(|roster| {
+            roster
+                .permissions
+                .get(agent_id)
+                .is_some_and(|capabilities| capabilities.contains(capability))
+        })
+    }
+
+    pub fn agents_with_capability(&self, capability: &str) -> Vec<String> {
+        self.read(|roster| {
+            roster
+                .permissions
+                .iter()
+                .filter(|(_, capabilities)| capabilities.contains(capability))
+                .map(|(agent_id, _)| agent_id.clone())
+                .collect()
+        })
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&AgentRoster) -> T) -> T {
+        let roster = self
+            .roster
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(&roster)
+    }
+}
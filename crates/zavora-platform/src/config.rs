@@ -1,34 +1,631 @@
-use anyhow::{Context, Result};
+use std::{net::SocketAddr, str::FromStr, time::Duration};
+
+use rust_decimal::Decimal;
+
+use crate::outbox::{
+    DEFAULT_MAX_PUBLISH_ATTEMPTS, DEFAULT_RETRY_BASE_DELAY, DEFAULT_RETRY_MAX_DELAY,
+    OutboxRetryPolicy,
+};
+
+const DEFAULT_LEAD_DEDUP_WINDOW_SECS: i64 = 86_400;
+const DEFAULT_ORDER_FULFILLMENT_SLA_HOURS: i32 = 72;
+const DEFAULT_THREE_WAY_MATCH_TOLERANCE_PCT: Decimal = Decimal::TWO;
+const DEFAULT_AGENT_RATE_LIMIT_PER_SEC: f64 = 20.0;
+const DEFAULT_AGENT_RATE_LIMIT_BURST: u32 = 40;
+const DEFAULT_DATABASE_MAX_CONNECTIONS: u32 = 10;
+const DEFAULT_DATABASE_ACQUIRE_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_SKILL_EXECUTOR_TIMEOUT_SECS: u64 = 30;
+const DATABASE_URL_SCHEMES: [&str; 2] = ["postgres://", "postgresql://"];
+const REDIS_URL_SCHEMES: [&str; 2] = ["redis://", "rediss://"];
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConfigError {
+    #[error("{var} is required")]
+    Missing { var: &'static str },
+    #[error("{var} must not be empty")]
+    Empty { var: &'static str },
+    #[error("{var} must start with one of {expected}")]
+    InvalidScheme { var: &'static str, expected: String },
+    #[error("{var} must be a socket address such as 0.0.0.0:8080, got {value:?}")]
+    InvalidAddr { var: &'static str, value: String },
+    #[error("{var} {reason}")]
+    Invalid {
+        var: &'static str,
+        reason: &'static str,
+    },
+}
 
 #[derive(Clone, Debug)]
 pub struct ServiceConfig {
     pub database_url: String,
+    pub database_max_connections: u32,
+    pub database_acquire_timeout: Duration,
     pub redis_url: String,
     pub http_addr: String,
+    pub otlp_endpoint: Option<String>,
+    pub reconciliation_namespace: String,
+    pub lead_dedup_window_secs: i64,
+    pub order_fulfillment_sla_hours: i32,
+    pub three_way_match_tolerance_pct: Decimal,
+    pub agent_token_secret: Option<String>,
+    pub agent_rate_limit_per_sec: f64,
+    pub agent_rate_limit_burst: u32,
+    pub evidence_signing_key: Option<String>,
+    pub shutdown_timeout_secs: u64,
+    pub outbox_retry: OutboxRetryPolicy,
+    pub skill_executor_command: Option<String>,
+    pub skill_executor_timeout: Duration,
 }
 
 impl ServiceConfig {
-    pub fn from_env(default_http_addr: &str) -> Result<Self> {
-        let database_url = std::env::var("DATABASE_URL").context("DATABASE_URL is required")?;
-        let redis_url = std::env::var("REDIS_URL").context("REDIS_URL is required")?;
-        let http_addr =
-            std::env::var("HTTP_ADDR").unwrap_or_else(|_| default_http_addr.to_string());
+    pub fn from_env(default_http_addr: &str) -> Result<Self, ConfigError> {
+        let database_url = url_from_env("DATABASE_URL", &DATABASE_URL_SCHEMES)?;
+        let (database_max_connections, database_acquire_timeout) = database_pool_from_env()?;
+        let redis_url = url_from_env("REDIS_URL", &REDIS_URL_SCHEMES)?;
+        let http_addr = http_addr_from_env(default_http_addr)?;
+        let otlp_endpoint = otlp_endpoint_from_env();
+        let reconciliation_namespace = reconciliation_namespace_from_env()?;
+        let lead_dedup_window_secs = lead_dedup_window_secs_from_env()?;
+        let order_fulfillment_sla_hours = order_fulfillment_sla_hours_from_env()?;
+        let three_way_match_tolerance_pct = three_way_match_tolerance_pct_from_env()?;
+        let (agent_rate_limit_per_sec, agent_rate_limit_burst) = agent_rate_limit_from_env()?;
 
         Ok(Self {
             database_url,
+            database_max_connections,
+            database_acquire_timeout,
             redis_url,
             http_addr,
+            otlp_endpoint,
+            reconciliation_namespace,
+            lead_dedup_window_secs,
+            order_fulfillment_sla_hours,
+            three_way_match_tolerance_pct,
+            agent_token_secret: agent_token_secret_from_env()?,
+            agent_rate_limit_per_sec,
+            agent_rate_limit_burst,
+            evidence_signing_key: evidence_signing_key_from_env(),
+            shutdown_timeout_secs: shutdown_timeout_secs_from_env()?,
+            outbox_retry: outbox_retry_from_env()?,
+            skill_executor_command: skill_executor_command_from_env()?,
+            skill_executor_timeout: skill_executor_timeout_from_env()?,
         })
     }
 
-    pub fn worker_from_env() -> Result<Self> {
-        let database_url = std::env::var("DATABASE_URL").context("DATABASE_URL is required")?;
-        let redis_url = std::env::var("REDIS_URL").context("REDIS_URL is required")?;
+    pub fn worker_from_env() -> Result<Self, ConfigError> {
+        let database_url = url_from_env("DATABASE_URL", &DATABASE_URL_SCHEMES)?;
+        let (database_max_connections, database_acquire_timeout) = database_pool_from_env()?;
+        let redis_url = url_from_env("REDIS_URL", &REDIS_URL_SCHEMES)?;
 
         Ok(Self {
             database_url,
+            database_max_connections,
+            database_acquire_timeout,
             redis_url,
             http_addr: String::new(),
+            otlp_endpoint: otlp_endpoint_from_env(),
+            reconciliation_namespace: reconciliation_namespace_from_env()?,
+            lead_dedup_window_secs: lead_dedup_window_secs_from_env()?,
+            order_fulfillment_sla_hours: order_fulfillment_sla_hours_from_env()?,
+            three_way_match_tolerance_pct: three_way_match_tolerance_pct_from_env()?,
+            agent_token_secret: agent_token_secret_from_env()?,
+            agent_rate_limit_per_sec: 0.0,
+            agent_rate_limit_burst: DEFAULT_AGENT_RATE_LIMIT_BURST,
+            evidence_signing_key: evidence_signing_key_from_env(),
+            shutdown_timeout_secs: shutdown_timeout_secs_from_env()?,
+            outbox_retry: outbox_retry_from_env()?,
+            skill_executor_command: skill_executor_command_from_env()?,
+            skill_executor_timeout: skill_executor_timeout_from_env()?,
         })
     }
+
+    pub fn require_agent_token_secret(&self) -> Result<String, ConfigError> {
+        self.agent_token_secret.clone().ok_or(ConfigError::Missing {
+            var: "AGENT_TOKEN_SECRET",
+        })
+    }
+
+    pub fn require_skill_executor_command(&self) -> Result<String, ConfigError> {
+        self.skill_executor_command
+            .clone()
+            .ok_or(ConfigError::Missing {
+                var: "SKILL_EXECUTOR_COMMAND",
+            })
+    }
+}
+
+fn url_from_env(var: &'static str, schemes: &[&str]) -> Result<String, ConfigError> {
+    parse_url(var, env_value(var).as_deref(), schemes)
+}
+
+fn parse_url(
+    var: &'static str,
+    value: Option<&str>,
+    schemes: &[&str],
+) -> Result<String, ConfigError> {
+    let value = value.ok_or(ConfigError::Missing { var })?;
+    validate_url(var, value.trim(), schemes)
+}
+
+fn validate_url(var: &'static str, value: &str, schemes: &[&str]) -> Result<String, ConfigError> {
+    if value.is_empty() {
+        return Err(ConfigError::Empty { var });
+    }
+    let has_scheme = schemes.iter().any(|scheme| {
+        value
+            .get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    });
+    if !has_scheme {
+        return Err(ConfigError::InvalidScheme {
+            var,
+            expected: schemes.join(" or "),
+        });
+    }
+
+    Ok(value.to_string())
+}
+
+fn http_addr_from_env(default_http_addr: &str) -> Result<String, ConfigError> {
+    let value = env_value("HTTP_ADDR").unwrap_or_else(|| default_http_addr.to_string());
+    validate_http_addr(value.trim())
+}
+
+fn validate_http_addr(value: &str) -> Result<String, ConfigError> {
+    if value.is_empty() {
+        return Err(ConfigError::Empty { var: "HTTP_ADDR" });
+    }
+    value
+        .parse::<SocketAddr>()
+        .map_err(|_| ConfigError::InvalidAddr {
+            var: "HTTP_ADDR",
+            value: value.to_string(),
+        })?;
+
+    Ok(value.to_string())
+}
+
+fn database_pool_from_env() -> Result<(u32, Duration), ConfigError> {
+    let max_connections = parse_positive(
+        "DATABASE_MAX_CONNECTIONS",
+        env_value("DATABASE_MAX_CONNECTIONS").as_deref(),
+        DEFAULT_DATABASE_MAX_CONNECTIONS,
+        "must be a positive integer",
+    )?;
+    let acquire_timeout_secs = parse_positive(
+        "DATABASE_ACQUIRE_TIMEOUT",
+        env_value("DATABASE_ACQUIRE_TIMEOUT").as_deref(),
+        DEFAULT_DATABASE_ACQUIRE_TIMEOUT_SECS,
+        "must be a positive number of seconds",
+    )?;
+
+    Ok((max_connections, Duration::from_secs(acquire_timeout_secs)))
+}
+
+fn otlp_endpoint_from_env() -> Option<String> {
+    optional_value(env_value("OTEL_EXPORTER_OTLP_ENDPOINT").as_deref())
+}
+
+fn agent_token_secret_from_env() -> Result<Option<String>, ConfigError> {
+    parse_secret(
+        "AGENT_TOKEN_SECRET",
+        env_value("AGENT_TOKEN_SECRET").as_deref(),
+    )
+}
+
+fn evidence_signing_key_from_env() -> Option<String> {
+    optional_value(env_value("EVIDENCE_SIGNING_KEY").as_deref())
+}
+
+fn agent_rate_limit_from_env() -> Result<(f64, u32), ConfigError> {
+    let rate_per_sec = parse_rate_per_sec(env_value("AGENT_RATE_LIMIT_PER_SEC").as_deref())?;
+    let burst = parse_positive(
+        "AGENT_RATE_LIMIT_BURST",
+        env_value("AGENT_RATE_LIMIT_BURST").as_deref(),
+        DEFAULT_AGENT_RATE_LIMIT_BURST,
+        "must be a positive integer",
+    )?;
+
+    Ok((rate_per_sec, burst))
+}
+
+fn reconciliation_namespace_from_env() -> Result<String, ConfigError> {
+    parse_reconciliation_namespace(env_value("RECONCILIATION_NAMESPACE").as_deref())
+}
+
+fn lead_dedup_window_secs_from_env() -> Result<i64, ConfigError> {
+    let window_secs = parse_value(
+        "LEAD_DEDUP_WINDOW_SECS",
+        env_value("LEAD_DEDUP_WINDOW_SECS").as_deref(),
+        DEFAULT_LEAD_DEDUP_WINDOW_SECS,
+        "must be a non-negative integer",
+    )?;
+    if window_secs < 0 {
+        return Err(ConfigError::Invalid {
+            var: "LEAD_DEDUP_WINDOW_SECS",
+            reason: "must be a non-negative integer",
+        });
+    }
+
+    Ok(window_secs)
+}
+
+fn order_fulfillment_sla_hours_from_env() -> Result<i32, ConfigError> {
+    parse_positive(
+        "ORDER_FULFILLMENT_SLA_HOURS",
+        env_value("ORDER_FULFILLMENT_SLA_HOURS").as_deref(),
+        DEFAULT_ORDER_FULFILLMENT_SLA_HOURS,
+        "must be a positive integer",
+    )
+}
+
+fn three_way_match_tolerance_pct_from_env() -> Result<Decimal, ConfigError> {
+    let tolerance_pct = parse_value(
+        "THREE_WAY_MATCH_TOLERANCE_PCT",
+        env_value("THREE_WAY_MATCH_TOLERANCE_PCT").as_deref(),
+        DEFAULT_THREE_WAY_MATCH_TOLERANCE_PCT,
+        "must be a decimal percentage between 0 and 100",
+    )?;
+    if tolerance_pct < Decimal::ZERO || tolerance_pct > Decimal::ONE_HUNDRED {
+        return Err(ConfigError::Invalid {
+            var: "THREE_WAY_MATCH_TOLERANCE_PCT",
+            reason: "must be a decimal percentage between 0 and 100",
+        });
+    }
+
+    Ok(tolerance_pct)
+}
+
+fn shutdown_timeout_secs_from_env() -> Result<u64, ConfigError> {
+    parse_positive(
+        "SHUTDOWN_TIMEOUT_SECS",
+        env_value("SHUTDOWN_TIMEOUT_SECS").as_deref(),
+        DEFAULT_SHUTDOWN_TIMEOUT_SECS,
+        "must be a positive integer",
+    )
+}
+
+fn skill_executor_command_from_env() -> Result<Option<String>, ConfigError> {
+    parse_secret(
+        "SKILL_EXECUTOR_COMMAND",
+        env_value("SKILL_EXECUTOR_COMMAND").as_deref(),
+    )
+}
+
+fn skill_executor_timeout_from_env() -> Result<Duration, ConfigError> {
+    parse_positive(
+        "SKILL_EXECUTOR_TIMEOUT_SECS",
+        env_value("SKILL_EXECUTOR_TIMEOUT_SECS").as_deref(),
+        DEFAULT_SKILL_EXECUTOR_TIMEOUT_SECS,
+        "must be a positive number of seconds",
+    )
+    .map(Duration::from_secs)
+}
+
+fn outbox_retry_from_env() -> Result<OutboxRetryPolicy, ConfigError> {
+    parse_outbox_retry(
+        env_value("OUTBOX_MAX_PUBLISH_ATTEMPTS").as_deref(),
+        env_value("OUTBOX_RETRY_BASE_DELAY_SECS").as_deref(),
+        env_value("OUTBOX_RETRY_MAX_DELAY_SECS").as_deref(),
+    )
+}
+
+fn parse_outbox_retry(
+    max_attempts: Option<&str>,
+    base_delay_secs: Option<&str>,
+    max_delay_secs: Option<&str>,
+) -> Result<OutboxRetryPolicy, ConfigError> {
+    let max_attempts = parse_positive(
+        "OUTBOX_MAX_PUBLISH_ATTEMPTS",
+        max_attempts,
+        DEFAULT_MAX_PUBLISH_ATTEMPTS,
+        "must be a positive integer",
+    )?;
+    let base_delay_secs = parse_positive(
+        "OUTBOX_RETRY_BASE_DELAY_SECS",
+        base_delay_secs,
+        DEFAULT_RETRY_BASE_DELAY.as_secs(),
+        "must be a positive number of seconds",
+    )?;
+    let max_delay_secs = parse_positive(
+        "OUTBOX_RETRY_MAX_DELAY_SECS",
+        max_delay_secs,
+        DEFAULT_RETRY_MAX_DELAY.as_secs(),
+        "must be a positive number of seconds",
+    )?;
+    if max_delay_secs < base_delay_secs {
+        return Err(ConfigError::Invalid {
+            var: "OUTBOX_RETRY_MAX_DELAY_SECS",
+            reason: "must not be less than OUTBOX_RETRY_BASE_DELAY_SECS",
+        });
+    }
+
+    Ok(OutboxRetryPolicy {
+        max_attempts,
+        base_delay: Duration::from_secs(base_delay_secs),
+        max_delay: Duration::from_secs(max_delay_secs),
+    })
+}
+
+fn env_value(var: &str) -> Option<String> {
+    std::env::var(var).ok()
+}
+
+fn optional_value(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn parse_secret(var: &'static str, value: Option<&str>) -> Result<Option<String>, ConfigError> {
+    match value.map(str::trim) {
+        None => Ok(None),
+        Some("") => Err(ConfigError::Empty { var }),
+        Some(secret) => Ok(Some(secret.to_string())),
+    }
+}
+
+fn parse_value<T: FromStr>(
+    var: &'static str,
+    value: Option<&str>,
+    default: T,
+    reason: &'static str,
+) -> Result<T, ConfigError> {
+    let Some(value) = value.map(str::trim) else {
+        return Ok(default);
+    };
+    if value.is_empty() {
+        return Err(ConfigError::Empty { var });
+    }
+    value
+        .parse::<T>()
+        .map_err(|_| ConfigError::Invalid { var, reason })
+}
+
+fn parse_positive<T: FromStr + PartialOrd + Default>(
+    var: &'static str,
+    value: Option<&str>,
+    default: T,
+    reason: &'static str,
+) -> Result<T, ConfigError> {
+    let parsed = parse_value(var, value, default, reason)?;
+    if parsed <= T::default() {
+        return Err(ConfigError::Invalid { var, reason });
+    }
+
+    Ok(parsed)
+}
+
+fn parse_rate_per_sec(value: Option<&str>) -> Result<f64, ConfigError> {
+    let reason = "must be a non-negative number";
+    let rate_per_sec = parse_value(
+        "AGENT_RATE_LIMIT_PER_SEC",
+        value,
+        DEFAULT_AGENT_RATE_LIMIT_PER_SEC,
+        reason,
+    )?;
+    if !rate_per_sec.is_finite() || rate_per_sec < 0.0 {
+        return Err(ConfigError::Invalid {
+            var: "AGENT_RATE_LIMIT_PER_SEC",
+            reason,
+        });
+    }
+
+    Ok(rate_per_sec)
+}
+
+fn parse_reconciliation_namespace(value: Option<&str>) -> Result<String, ConfigError> {
+    let namespace = value.unwrap_or_default().trim().to_ascii_lowercase();
+    if !namespace
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+    {
+        return Err(ConfigError::Invalid {
+            var: "RECONCILIATION_NAMESPACE",
+            reason: "may only contain letters, digits, and '-'",
+        });
+    }
+
+    Ok(namespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_and_empty_urls_name_the_variable() {
+        assert_eq!(
+            parse_url("DATABASE_URL", None, &DATABASE_URL_SCHEMES),
+            Err(ConfigError::Missing {
+                var: "DATABASE_URL"
+            })
+        );
+        assert_eq!(
+            parse_url("REDIS_URL", Some("  "), &REDIS_URL_SCHEMES),
+            Err(ConfigError::Empty { var: "REDIS_URL" })
+        );
+    }
+
+    #[test]
+    fn urls_must_use_an_expected_scheme() {
+        assert_eq!(
+            validate_url("DATABASE_URL", "mysql://db/zavora", &DATABASE_URL_SCHEMES),
+            Err(ConfigError::InvalidScheme {
+                var: "DATABASE_URL",
+                expected: "postgres:// or postgresql://".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_url(
+                "DATABASE_URL",
+                Some(" PostgreSQL://zavora@db/zavora "),
+                &DATABASE_URL_SCHEMES
+            ),
+            Ok("PostgreSQL://zavora@db/zavora".to_string())
+        );
+        assert_eq!(
+            validate_url("REDIS_URL", "rediss://cache:6380", &REDIS_URL_SCHEMES),
+            Ok("rediss://cache:6380".to_string())
+        );
+    }
+
+    #[test]
+    fn http_addr_must_be_a_socket_address() {
+        assert_eq!(
+            validate_http_addr("localhost"),
+            Err(ConfigError::InvalidAddr {
+                var: "HTTP_ADDR",
+                value: "localhost".to_string(),
+            })
+        );
+        assert_eq!(
+            validate_http_addr(""),
+            Err(ConfigError::Empty { var: "HTTP_ADDR" })
+        );
+        assert_eq!(
+            validate_http_addr("0.0.0.0:8080"),
+            Ok("0.0.0.0:8080".to_string())
+        );
+    }
+
+    #[test]
+    fn numeric_settings_default_when_unset_and_reject_bad_values() {
+        assert_eq!(
+            parse_positive("DATABASE_MAX_CONNECTIONS", None, 10u32, "must be positive"),
+            Ok(10)
+        );
+        assert_eq!(
+            parse_positive(
+                "DATABASE_MAX_CONNECTIONS",
+                Some(" 25 "),
+                10u32,
+                "must be positive"
+            ),
+            Ok(25)
+        );
+        assert_eq!(
+            parse_positive(
+                "DATABASE_MAX_CONNECTIONS",
+                Some("0"),
+                10u32,
+                "must be positive"
+            ),
+            Err(ConfigError::Invalid {
+                var: "DATABASE_MAX_CONNECTIONS",
+                reason: "must be positive",
+            })
+        );
+        assert_eq!(
+            parse_positive(
+                "ORDER_FULFILLMENT_SLA_HOURS",
+                Some("-4"),
+                72i32,
+                "must be positive"
+            ),
+            Err(ConfigError::Invalid {
+                var: "ORDER_FULFILLMENT_SLA_HOURS",
+                reason: "must be positive",
+            })
+        );
+        assert_eq!(
+            parse_value(
+                "SHUTDOWN_TIMEOUT_SECS",
+                Some("soon"),
+                30u64,
+                "must be positive"
+            ),
+            Err(ConfigError::Invalid {
+                var: "SHUTDOWN_TIMEOUT_SECS",
+                reason: "must be positive",
+            })
+        );
+        assert_eq!(
+            parse_value("SHUTDOWN_TIMEOUT_SECS", Some(""), 30u64, "must be positive"),
+            Err(ConfigError::Empty {
+                var: "SHUTDOWN_TIMEOUT_SECS"
+            })
+        );
+    }
+
+    #[test]
+    fn rate_limit_accepts_zero_but_not_negative_or_infinite() {
+        assert_eq!(
+            parse_rate_per_sec(None),
+            Ok(DEFAULT_AGENT_RATE_LIMIT_PER_SEC)
+        );
+        assert_eq!(parse_rate_per_sec(Some("0")), Ok(0.0));
+        for value in ["-1", "inf", "fast"] {
+            assert!(matches!(
+                parse_rate_per_sec(Some(value)),
+                Err(ConfigError::Invalid {
+                    var: "AGENT_RATE_LIMIT_PER_SEC",
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn agent_token_secret_is_optional_but_not_blank() {
+        assert_eq!(parse_secret("AGENT_TOKEN_SECRET", None), Ok(None));
+        assert_eq!(
+            parse_secret("AGENT_TOKEN_SECRET", Some(" s3cret ")),
+            Ok(Some("s3cret".to_string()))
+        );
+        assert_eq!(
+            parse_secret("AGENT_TOKEN_SECRET", Some("   ")),
+            Err(ConfigError::Empty {
+                var: "AGENT_TOKEN_SECRET"
+            })
+        );
+    }
+
+    #[test]
+    fn outbox_retry_defaults_and_validates_the_delay_cap() {
+        assert_eq!(
+            parse_outbox_retry(None, None, None),
+            Ok(OutboxRetryPolicy::default())
+        );
+        assert_eq!(
+            parse_outbox_retry(Some("3"), Some("1"), Some("60")),
+            Ok(OutboxRetryPolicy {
+                max_attempts: 3,
+                base_delay: Duration::from_secs(1),
+                max_delay: Duration::from_secs(60),
+            })
+        );
+        assert_eq!(
+            parse_outbox_retry(Some("0"), None, None),
+            Err(ConfigError::Invalid {
+                var: "OUTBOX_MAX_PUBLISH_ATTEMPTS",
+                reason: "must be a positive integer",
+            })
+        );
+        assert!(matches!(
+            parse_outbox_retry(None, Some("120"), Some("60")),
+            Err(ConfigError::Invalid {
+                var: "OUTBOX_RETRY_MAX_DELAY_SECS",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn reconciliation_namespace_is_normalized_and_validated() {
+        assert_eq!(parse_reconciliation_namespace(None), Ok(String::new()));
+        assert_eq!(
+            parse_reconciliation_namespace(Some(" Tenant-A ")),
+            Ok("tenant-a".to_string())
+        );
+        assert!(matches!(
+            parse_reconciliation_namespace(Some("tenant_a")),
+            Err(ConfigError::Invalid {
+                var: "RECONCILIATION_NAMESPACE",
+                ..
+            })
+        ));
+    }
 }
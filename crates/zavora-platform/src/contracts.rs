@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use zavora_core::Currency;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateOrderRequest {
@@ -11,8 +12,7 @@ pub struct CreateOrderRequest {
     pub item_code: String,
     pub quantity: Decimal,
     pub unit_price: Decimal,
-    pub currency: String,
-    #[serde(default = "default_requesting_agent")]
+    pub currency: Currency,
     pub requested_by_agent_id: String,
 }
 
@@ -21,7 +21,6 @@ pub struct CreateLeadRequest {
     pub contact_email: String,
     pub source_channel: String,
     pub note: Option<String>,
-    #[serde(default = "default_requesting_agent")]
     pub requested_by_agent_id: String,
 }
 
@@ -30,6 +29,8 @@ pub struct CreateLeadResponse {
     pub lead_id: Uuid,
     pub status: String,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub deduplicated: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,9 +42,8 @@ pub struct CreateOpportunityRequest {
     pub item_code: String,
     pub quantity: Decimal,
     pub target_unit_price: Decimal,
-    pub currency: String,
+    pub currency: Currency,
     pub risk_class: Option<String>,
-    #[serde(default = "default_requesting_agent")]
     pub requested_by_agent_id: String,
 }
 
@@ -57,21 +57,51 @@ pub struct CreateOpportunityResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateQuoteRequest {
     pub opportunity_id: Uuid,
-    pub unit_price: Decimal,
+    #[serde(default)]
+    pub unit_price: Option<Decimal>,
     pub quantity: Option<Decimal>,
-    pub currency: Option<String>,
+    pub currency: Option<Currency>,
     pub payment_terms_days: Option<i32>,
     pub valid_for_days: Option<i64>,
     pub risk_note: Option<String>,
-    #[serde(default = "default_requesting_agent")]
+    pub lines: Option<Vec<QuoteLineRequest>>,
     pub requested_by_agent_id: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteLineRequest {
+    pub transaction_type: Option<String>,
+    pub item_code: String,
+    pub quantity: Decimal,
+    pub unit_price: Option<Decimal>,
+    pub currency: Option<Currency>,
+    pub discount_pct: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteLineView {
+    pub id: Uuid,
+    pub line_no: i32,
+    pub transaction_type: String,
+    pub item_code: String,
+    pub quantity: Decimal,
+    pub unit_price: Decimal,
+    pub currency: String,
+    pub discount_pct: Decimal,
+    pub line_amount: Decimal,
+    pub applied_tier_id: Option<Uuid>,
+    pub order_id: Option<Uuid>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateQuoteResponse {
     pub quote_id: Uuid,
     pub opportunity_id: Uuid,
     pub status: String,
+    pub unit_price: Decimal,
+    pub applied_tier_id: Option<Uuid>,
+    pub lines: Vec<QuoteLineView>,
+    pub total_amount: Decimal,
     pub valid_until: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
 }
@@ -81,7 +111,6 @@ pub struct AcceptQuoteRequest {
     pub accepted_by: String,
     pub acceptance_channel: String,
     pub proof_ref: String,
-    #[serde(default = "default_requesting_agent")]
     pub requested_by_agent_id: String,
 }
 
@@ -91,8 +120,10 @@ pub struct AcceptQuoteResponse {
     pub opportunity_id: Uuid,
     pub acceptance_id: Uuid,
     pub order_id: Uuid,
+    pub order_ids: Vec<Uuid>,
     pub status: String,
     pub escalation_id: Option<Uuid>,
+    pub escalation_ids: Vec<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,11 +133,18 @@ pub struct CreateOrderResponse {
     pub transaction_type: String,
     pub requested_by_agent_id: String,
     pub escalation_id: Option<Uuid>,
+    pub sla_deadline_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderCreatedEvent {
     pub order_id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traceparent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracestate: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,22 +161,45 @@ pub struct BoardPack {
     pub orders_fulfilled: i64,
     pub orders_open: i64,
     pub orders_pending_approval: i64,
+    pub orders_sla_breached: i64,
     pub leads_total: i64,
+    pub leads_by_status: Vec<LeadStatusCount>,
+    pub avg_lead_score: Decimal,
     pub opportunities_open: i64,
     pub quotes_issued: i64,
     pub quotes_accepted: i64,
     pub governance_escalations_pending: i64,
+    pub overdue_escalations: i64,
+    pub corrective_actions_open: i64,
+    pub corrective_actions_overdue: i64,
+    pub corrective_actions_by_responsible_agent: Vec<CorrectiveActionOwnerSummary>,
     pub revenue: Decimal,
     pub cash_collected: Decimal,
     pub inventory_value: Decimal,
     pub autonomy_operating_cost: Decimal,
     pub margin_after_autonomy_cost: Decimal,
     pub revenue_to_agent_payroll_ratio: Decimal,
+    pub win_rate_pct: Decimal,
+    pub credit_utilization_pct: Decimal,
+    pub top_performing_agent_id: Option<String>,
     pub finops_reconciliation_status: String,
     pub finops_reconciliation_variance_pct: Decimal,
     pub finops_last_reconciled_at: Option<DateTime<Utc>>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeadStatusCount {
+    pub status: String,
+    pub leads: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrectiveActionOwnerSummary {
+    pub responsible_agent_id: Option<String>,
+    pub open_actions: i64,
+    pub overdue_actions: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryWriteRequest {
     pub agent_name: String,
@@ -189,6 +250,28 @@ fn default_transaction_type() -> String {
     "PRODUCT".to_string()
 }
 
-fn default_requesting_agent() -> String {
-    "sales-agent".to_string()
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GovernanceEscalationEvent {
+    pub event: String,
+    pub escalation: serde_json::Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_without_an_actor_are_rejected() {
+        let payload = serde_json::json!({
+            "customer_email": "buyer@example.com",
+            "item_code": "SKU-1",
+            "quantity": "1",
+            "unit_price": "10.00",
+            "currency": "USD"
+        });
+
+        let err = serde_json::from_value::<CreateOrderRequest>(payload).expect_err("missing actor");
+
+        assert!(err.to_string().contains("requested_by_agent_id"));
+    }
 }
@@ -1,11 +1,63 @@
 use anyhow::Result;
 use sqlx::{PgPool, postgres::PgPoolOptions};
 
-pub async fn connect_database(database_url: &str) -> Result<PgPool> {
-    let pool = PgPoolOptions::new()
-        .max_connections(10)
-        .connect(database_url)
-        .await?;
+use crate::config::ServiceConfig;
+
+pub async fn connect_database(config: &ServiceConfig) -> Result<PgPool> {
+    let pool = pool_options(config).connect(&config.database_url).await?;
 
     Ok(pool)
 }
+
+fn pool_options(config: &ServiceConfig) -> PgPoolOptions {
+    PgPoolOptions::new()
+        .max_connections(config.database_max_connections)
+        .acquire_timeout(config.database_acquire_timeout)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rust_decimal::Decimal;
+
+    use super::*;
+    use crate::OutboxRetryPolicy;
+
+    fn config(max_connections: u32, acquire_timeout: Duration) -> ServiceConfig {
+        ServiceConfig {
+            database_url: "postgres://zavora@localhost:5432/zavora".to_string(),
+            database_max_connections: max_connections,
+            database_acquire_timeout: acquire_timeout,
+            redis_url: "redis://localhost:6379".to_string(),
+            http_addr: "127.0.0.1:0".to_string(),
+            otlp_endpoint: None,
+            reconciliation_namespace: String::new(),
+            lead_dedup_window_secs: 0,
+            order_fulfillment_sla_hours: 24,
+            three_way_match_tolerance_pct: Decimal::ZERO,
+            agent_token_secret: None,
+            agent_rate_limit_per_sec: 1.0,
+            agent_rate_limit_burst: 1,
+            evidence_signing_key: None,
+            shutdown_timeout_secs: 1,
+            outbox_retry: OutboxRetryPolicy::default(),
+            skill_executor_command: None,
+            skill_executor_timeout: Duration::from_secs(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn configured_pool_limits_reach_the_built_pool() {
+        let config = config(3, Duration::from_millis(1500));
+        let pool = pool_options(&config)
+            .connect_lazy(&config.database_url)
+            .unwrap();
+
+        assert_eq!(pool.options().get_max_connections(), 3);
+        assert_eq!(
+            pool.options().get_acquire_timeout(),
+            Duration::from_millis(1500)
+        );
+    }
+}
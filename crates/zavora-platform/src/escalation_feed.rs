@@ -0,0 +1,106 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+use zavora_core::{DomainEventKind, EscalationDecidedPayload, EscalationOpenedPayload};
+
+use crate::{
+    contracts::GovernanceEscalationEvent, event_store::append_aggregate_event,
+    outbox::enqueue_event,
+};
+
+pub const ESCALATION_FEED_CHANNEL: &str = "governance.escalations";
+pub const ESCALATION_CREATED: &str = "new_escalation";
+pub const ESCALATION_DECIDED: &str = "escalation_decided";
+pub const DEFAULT_ESCALATION_SLA_HOURS: i64 = 24;
+
+pub async fn enqueue_escalation_event(
+    tx: &mut Transaction<'_, Postgres>,
+    escalation_id: Uuid,
+    event: &str,
+) -> Result<()> {
+    if event == ESCALATION_CREATED {
+        sqlx::query(
+            r#"
+            UPDATE governance_escalations e
+            SET sla_deadline_at = e.created_at + COALESCE(
+                    (
+                        SELECT t.sla_hours
+                        FROM governance_thresholds t
+                        WHERE t.action_type = e.action_type
+                    ),
+                    $2
+                ) * INTERVAL '1 hour'
+            WHERE e.id = $1
+            "#,
+        )
+        .bind(escalation_id)
+        .bind(DEFAULT_ESCALATION_SLA_HOURS)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    let escalation = sqlx::query_scalar::<_, Value>(
+        r#"
+        SELECT json_build_object(
+            'escalation_id', id,
+            'action_type', action_type,
+            'reference_type', reference_type,
+            'reference_id', reference_id,
+            'status', status,
+            'reason_code', reason_code,
+            'amount', amount::text,
+            'currency', currency,
+            'requested_by_agent_id', requested_by_agent_id,
+            'created_at', created_at,
+            'decided_at', decided_at,
+            'decided_by_agent_id', decided_by_agent_id,
+            'decision_note', decision_note,
+            'sla_deadline_at', sla_deadline_at,
+            'sla_status', sla_status
+        )::jsonb
+        FROM governance_escalations
+        WHERE id = $1
+        "#,
+    )
+    .bind(escalation_id)
+    .fetch_optional(&mut **tx)
+    .await?
+    .context("escalation not found")?;
+
+    if event == ESCALATION_CREATED {
+        let payload: EscalationOpenedPayload = serde_json::from_value(escalation.clone())?;
+        append_aggregate_event(
+            &mut **tx,
+            escalation_id,
+            DomainEventKind::EscalationOpened,
+            Utc::now(),
+            &payload,
+        )
+        .await?;
+    } else if event == ESCALATION_DECIDED {
+        let payload: EscalationDecidedPayload = serde_json::from_value(escalation.clone())?;
+        append_aggregate_event(
+            &mut **tx,
+            escalation_id,
+            DomainEventKind::EscalationDecided,
+            payload.decided_at.unwrap_or_else(Utc::now),
+            &payload,
+        )
+        .await?;
+    }
+
+    enqueue_event(
+        tx,
+        ESCALATION_FEED_CHANNEL,
+        escalation_id,
+        &GovernanceEscalationEvent {
+            event: event.to_string(),
+            escalation,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Value, json};
+use sqlx::{PgExecutor, PgPool, Row};
+use uuid::Uuid;
+use zavora_core::{
+    DOMAIN_EVENT_SCHEMA_VERSION, DomainEvent, DomainEventKind, EventEnvelope, EventStore,
+    EventUpcastError, upcast,
+};
+
+#[derive(Clone)]
+pub struct PgEventStore {
+    pool: PgPool,
+}
+
+impl PgEventStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EventStore for PgEventStore {
+    async fn append(&self, stream_id: Uuid, event: DomainEvent) -> Result<EventEnvelope> {
+        append_domain_event(&self.pool, stream_id, &event).await
+    }
+
+    async fn stream(&self, stream_id: Uuid) -> Result<Vec<EventEnvelope>> {
+        self.stream_after(stream_id, 0).await
+    }
+
+    async fn stream_after(
+        &self,
+        stream_id: Uuid,
+        after_sequence: i64,
+    ) -> Result<Vec<EventEnvelope>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                sequence, stream_id, schema_version, id, aggregate_id, kind_json, payload_json,
+                occurred_at, stored_at
+            FROM domain_events
+            WHERE stream_id = $1
+              AND sequence > $2
+            ORDER BY sequence
+            "#,
+        )
+        .bind(stream_id)
+        .bind(after_sequence)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut envelopes = Vec::with_capacity(rows.len());
+        for row in rows {
+            let stored = StoredEvent {
+                schema_version: row.try_get("schema_version")?,
+                id: row.try_get("id")?,
+                aggregate_id: row.try_get("aggregate_id")?,
+                kind_json: row.try_get("kind_json")?,
+                occurred_at: row.try_get("occurred_at")?,
+                payload_json: row.try_get("payload_json")?,
+            };
+            envelopes.push(EventEnvelope {
+                sequence: row.try_get("sequence")?,
+                stream_id: row.try_get("stream_id")?,
+                schema_version: DOMAIN_EVENT_SCHEMA_VERSION,
+                event: stored.into_event(upcast)?,
+                stored_at: row.try_get("stored_at")?,
+            });
+        }
+
+        Ok(envelopes)
+    }
+}
+
+struct StoredEvent {
+    schema_version: i32,
+    id: Uuid,
+    aggregate_id: Uuid,
+    kind_json: Value,
+    occurred_at: DateTime<Utc>,
+    payload_json: Value,
+}
+
+impl StoredEvent {
+    fn into_event(
+        self,
+        upcast: impl FnOnce(Value, u32) -> Result<DomainEvent, EventUpcastError>,
+    ) -> Result<DomainEvent> {
+        let schema_version = u32::try_from(self.schema_version)?;
+        let raw = json!({
+            "id": self.id,
+            "aggregate_id": self.aggregate_id,
+            "kind": self.kind_json,
+            "occurred_at": self.occurred_at,
+            "payload": self.payload_json,
+        });
+        Ok(upcast(raw, schema_version)?)
+    }
+}
+
+pub async fn append_domain_event<'c, E>(
+    executor: E,
+    stream_id: Uuid,
+    event: &DomainEvent,
+) -> Result<EventEnvelope>
+where
+    E: PgExecutor<'c>,
+{
+    let row = sqlx::query(
+        r#"
+        INSERT INTO domain_events (
+            id, stream_id, aggregate_id, kind, kind_json, payload_json, occurred_at, stored_at,
+            schema_version
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING sequence, stored_at
+        "#,
+    )
+    .bind(event.id)
+    .bind(stream_id)
+    .bind(event.aggregate_id)
+    .bind(event.kind.name())
+    .bind(serde_json::to_value(&event.kind)?)
+    .bind(&event.payload)
+    .bind(event.occurred_at)
+    .bind(Utc::now())
+    .bind(i32::try_from(DOMAIN_EVENT_SCHEMA_VERSION)?)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(EventEnvelope {
+        sequence: row.try_get("sequence")?,
+        stream_id,
+        schema_version: DOMAIN_EVENT_SCHEMA_VERSION,
+        event: event.clone(),
+        stored_at: row.try_get("stored_at")?,
+    })
+}
+
+pub async fn append_aggregate_event<'c, E, T>(
+    executor: E,
+    aggregate_id: Uuid,
+    kind: DomainEventKind,
+    occurred_at: DateTime<Utc>,
+    payload: &T,
+) -> Result<EventEnvelope>
+where
+    E: PgExecutor<'c>,
+    T: Serialize,
+{
+    let event = DomainEvent {
+        id: Uuid::new_v4(),
+        aggregate_id,
+        kind,
+        occurred_at,
+        payload: serde_json::to_value(payload)?,
+    };
+    append_domain_event(executor, aggregate_id, &event).await
+}
+
+#[cfg(test)]
+mod tests {
+    use zavora_core::{EventUpcaster, upcast_with};
+
+    use super::*;
+
+    fn stored(schema_version: i32, payload_json: Value) -> StoredEvent {
+        StoredEvent {
+            schema_version,
+            id: Uuid::new_v4(),
+            aggregate_id: Uuid::new_v4(),
+            kind_json: json!("OrderFailed"),
+            occurred_at: Utc::now(),
+            payload_json,
+        }
+    }
+
+    fn split_reason_code(mut event: Value) -> Value {
+        if let Some(reason) = event["payload"]["failure_reason"].as_str() {
+            let (code, _) = reason.split_once(':').unwrap_or((reason, ""));
+            event["payload"]["failure_code"] = Value::String(code.trim().to_string());
+        }
+        event
+    }
+
+    #[test]
+    fn replay_returns_upcast_payload_for_older_rows() {
+        let row = stored(
+            1,
+            json!({"failure_reason": "STOCK_SHORTAGE: 3 units short"}),
+        );
+        let (id, aggregate_id) = (row.id, row.aggregate_id);
+        let upcasters: &[(u32, EventUpcaster)] = &[(1, split_reason_code)];
+
+        let event = row
+            .into_event(|raw, version| upcast_with(upcasters, 2, raw, version))
+            .unwrap();
+
+        assert_eq!(event.id, id);
+        assert_eq!(event.aggregate_id, aggregate_id);
+        assert!(matches!(event.kind, DomainEventKind::OrderFailed));
+        assert_eq!(event.payload["failure_code"], "STOCK_SHORTAGE");
+        assert_eq!(
+            event.payload["failure_reason"],
+            "STOCK_SHORTAGE: 3 units short"
+        );
+    }
+
+    #[test]
+    fn replay_of_current_rows_leaves_payload_unchanged() {
+        let payload = json!({"order_id": Uuid::nil(), "failure_reason": "CANCELLED"});
+        let event = stored(DOMAIN_EVENT_SCHEMA_VERSION as i32, payload.clone())
+            .into_event(upcast)
+            .unwrap();
+        assert_eq!(event.payload, payload);
+    }
+
+    #[test]
+    fn replay_rejects_invalid_stored_versions() {
+        assert!(stored(-1, json!({})).into_event(upcast).is_err());
+        assert!(
+            stored(DOMAIN_EVENT_SCHEMA_VERSION as i32 + 1, json!({}))
+                .into_event(upcast)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::time::{Duration, Instant};
+
+use axum::http::StatusCode;
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::{outbox::dead_letter_count, redis_bus::RedisBus};
+
+const CHECK_TIMEOUT: Duration = Duration::from_millis(500);
+const METRICS_ENDPOINT: &str = "/metrics";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub status: String,
+    pub checks: HealthChecks,
+    pub failed_dependencies: Vec<String>,
+    pub dead_letter_count: Option<i64>,
+    pub version: String,
+    pub uptime_secs: u64,
+    pub metrics_endpoint: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthChecks {
+    pub database: DependencyCheck,
+    pub redis: DependencyCheck,
+    pub event_store: EventStoreCheck,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyCheck {
+    pub status: String,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EventStoreCheck {
+    pub status: String,
+}
+
+pub async fn check_health(
+    pool: &PgPool,
+    redis: &RedisBus,
+    version: &str,
+    started_at: Instant,
+) -> (StatusCode, HealthReport) {
+    let (database, redis, event_store, dead_letters) = tokio::join!(
+        check_database(pool),
+        check_redis(redis),
+        check_event_store(pool),
+        tokio::time::timeout(CHECK_TIMEOUT, dead_letter_count(pool))
+    );
+
+    let results = [
+        ("database", database.status == "ok"),
+        ("redis", redis.status == "ok"),
+        ("event_store", event_store.status == "ok"),
+    ];
+    let failed_dependencies: Vec<String> = results
+        .iter()
+        .filter(|(_, ok)| !ok)
+        .map(|(name, _)| name.to_string())
+        .collect();
+    let status = if failed_dependencies.is_empty() {
+        "ok"
+    } else if failed_dependencies.len() < results.len() {
+        "degraded"
+    } else {
+        "down"
+    };
+    let status_code = if failed_dependencies.is_empty() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status_code,
+        HealthReport {
+            status: status.to_string(),
+            checks: HealthChecks {
+                database,
+                redis,
+                event_store,
+            },
+            failed_dependencies,
+            dead_letter_count: dead_letters.ok().and_then(Result::ok),
+            version: version.to_string(),
+            uptime_secs: started_at.elapsed().as_secs(),
+            metrics_endpoint: METRICS_ENDPOINT.to_string(),
+        },
+    )
+}
+
+async fn check_database(pool: &PgPool) -> DependencyCheck {
+    let started = Instant::now();
+    let outcome = tokio::time::timeout(
+        CHECK_TIMEOUT,
+        sqlx::query_scalar::<_, i32>("SELECT 1").fetch_one(pool),
+    )
+    .await;
+
+    dependency_check(
+        started,
+        outcome.map(|result| result.map(|_| ()).map_err(Into::into)),
+    )
+}
+
+async fn check_redis(redis: &RedisBus) -> DependencyCheck {
+    let started = Instant::now();
+    let outcome = tokio::time::timeout(CHECK_TIMEOUT, redis.ping()).await;
+
+    dependency_check(started, outcome)
+}
+
+async fn check_event_store(pool: &PgPool) -> EventStoreCheck {
+    let outcome = tokio::time::timeout(
+        CHECK_TIMEOUT,
+        sqlx::query_scalar::<_, bool>(
+            "SELECT to_regclass('public.orders') IS NOT NULL AND to_regclass('public.journals') IS NOT NULL",
+        )
+        .fetch_one(pool),
+    )
+    .await;
+
+    let status = match outcome {
+        Ok(Ok(true)) => "ok",
+        _ => "error",
+    };
+
+    EventStoreCheck {
+        status: status.to_string(),
+    }
+}
+
+fn dependency_check(
+    started: Instant,
+    outcome: Result<anyhow::Result<()>, tokio::time::error::Elapsed>,
+) -> DependencyCheck {
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let (status, error) = match outcome {
+        Ok(Ok(())) => ("ok", None),
+        Ok(Err(err)) => ("error", Some(err.to_string())),
+        Err(_) => ("timeout", Some("check exceeded 500ms".to_string())),
+    };
+
+    DependencyCheck {
+        status: status.to_string(),
+        latency_ms,
+        error,
+    }
+}
@@ -1,15 +1,50 @@
+pub mod agent_registry;
 pub mod config;
 pub mod contracts;
 pub mod db;
+pub mod escalation_feed;
+pub mod event_store;
+pub mod health;
+pub mod metrics;
+pub mod outbox;
+pub mod rate_limit;
 pub mod redis_bus;
+pub mod request_log;
+pub mod shutdown;
+pub mod telemetry;
+#[cfg(any(test, feature = "test-support"))]
+pub mod testing;
 
-pub use config::ServiceConfig;
+pub use agent_registry::{AgentRegistry, AgentRoster};
+pub use config::{ConfigError, ServiceConfig};
 pub use contracts::{
-    AcceptQuoteRequest, AcceptQuoteResponse, BoardPack, CreateLeadRequest, CreateLeadResponse,
-    CreateOpportunityRequest, CreateOpportunityResponse, CreateOrderRequest, CreateOrderResponse,
-    CreateQuoteRequest, CreateQuoteResponse, MemorySearchHit, MemorySearchRequest,
+    AcceptQuoteRequest, AcceptQuoteResponse, BoardPack, CorrectiveActionOwnerSummary,
+    CreateLeadRequest, CreateLeadResponse, CreateOpportunityRequest, CreateOpportunityResponse,
+    CreateOrderRequest, CreateOrderResponse, CreateQuoteRequest, CreateQuoteResponse,
+    GovernanceEscalationEvent, LeadStatusCount, MemorySearchHit, MemorySearchRequest,
     MemorySearchResponse, MemoryWriteRequest, MemoryWriteResponse, OrderCreatedEvent,
-    OrderFulfilledEvent,
+    OrderFulfilledEvent, QuoteLineRequest, QuoteLineView,
 };
 pub use db::connect_database;
+pub use escalation_feed::{
+    DEFAULT_ESCALATION_SLA_HOURS, ESCALATION_CREATED, ESCALATION_DECIDED, ESCALATION_FEED_CHANNEL,
+    enqueue_escalation_event,
+};
+pub use event_store::{PgEventStore, append_aggregate_event, append_domain_event};
+pub use health::{DependencyCheck, EventStoreCheck, HealthChecks, HealthReport, check_health};
+pub use metrics::{METRICS_CONTENT_TYPE, Metrics, track_metrics};
+pub use outbox::{
+    OutboxRetryPolicy, dead_letter_count, drain_outbox, enqueue_event, publish_pending,
+    run_outbox_relay,
+};
+pub use rate_limit::AgentRateLimiter;
 pub use redis_bus::RedisBus;
+pub use request_log::{
+    CORRELATION_ID_HEADER, RequestLog, RequestLogEntry, current_correlation_id, current_request_id,
+    log_requests, set_request_agent_id,
+};
+pub use shutdown::{Shutdown, serve_with_graceful_shutdown};
+pub use telemetry::{
+    RecordRequestOutcome, RequestSpan, TelemetryGuard, TraceContextHeaders, current_trace_context,
+    init_tracing,
+};
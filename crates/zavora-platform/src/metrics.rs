@@ -0,0 +1,229 @@
+use std::time::Instant;
+
+use anyhow::Result;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use prometheus::{
+    Gauge, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder, core::Collector,
+};
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use sqlx::PgPool;
+use tracing::warn;
+
+pub const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+const LATENCY_BUCKETS_SECS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+const REQUEST_DURATION_METRIC: &str = "zavora_request_duration_seconds";
+const REQUESTS_TOTAL_METRIC: &str = "http_requests_total";
+const ESCALATIONS_PENDING_METRIC: &str = "zavora_escalations_pending";
+const JOURNAL_BALANCE_DEVIATION_METRIC: &str = "zavora_journal_balance_deviation";
+const DB_POOL_CONNECTIONS_METRIC: &str = "zavora_db_pool_connections";
+const REQUEST_LABELS: [&str; 3] = ["path", "method", "status"];
+
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    requests_total: IntCounterVec,
+    request_duration: HistogramVec,
+    db_pool_connections: GaugeVec,
+    escalations_pending: IntGauge,
+    journal_balance_deviation: Gauge,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        let registry = Registry::new();
+        let metrics = Self {
+            requests_total: IntCounterVec::new(
+                Opts::new(
+                    REQUESTS_TOTAL_METRIC,
+                    "HTTP requests by path, method, and status.",
+                ),
+                &REQUEST_LABELS,
+            )
+            .expect("valid request counter"),
+            request_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    REQUEST_DURATION_METRIC,
+                    "HTTP handler latency by path, method, and status.",
+                )
+                .buckets(LATENCY_BUCKETS_SECS.to_vec()),
+                &REQUEST_LABELS,
+            )
+            .expect("valid request histogram"),
+            db_pool_connections: GaugeVec::new(
+                Opts::new(
+                    DB_POOL_CONNECTIONS_METRIC,
+                    "Postgres pool connections by state.",
+                ),
+                &["state"],
+            )
+            .expect("valid pool gauge"),
+            escalations_pending: IntGauge::new(
+                ESCALATIONS_PENDING_METRIC,
+                "Governance escalations awaiting a decision.",
+            )
+            .expect("valid escalation gauge"),
+            journal_balance_deviation: Gauge::new(
+                JOURNAL_BALANCE_DEVIATION_METRIC,
+                "Trial balance net_balance (total debits minus total credits); 0 when the ledger balances.",
+            )
+            .expect("valid journal gauge"),
+            registry,
+        };
+        for collector in [
+            Box::new(metrics.requests_total.clone()) as Box<dyn Collector>,
+            Box::new(metrics.request_duration.clone()),
+            Box::new(metrics.db_pool_connections.clone()),
+            Box::new(metrics.escalations_pending.clone()),
+            Box::new(metrics.journal_balance_deviation.clone()),
+        ] {
+            metrics
+                .registry
+                .register(collector)
+                .expect("platform metrics are registered once per registry");
+        }
+        metrics
+    }
+}
+
+impl Metrics {
+    pub fn register<C>(&self, collector: C) -> prometheus::Result<C>
+    where
+        C: Collector + Clone + 'static,
+    {
+        self.registry.register(Box::new(collector.clone()))?;
+        Ok(collector)
+    }
+
+    pub async fn observe_database(&self, pool: &PgPool) -> Result<()> {
+        let idle = pool.num_idle() as f64;
+        let active = (f64::from(pool.size()) - idle).max(0.0);
+        self.db_pool_connections
+            .with_label_values(&["active"])
+            .set(active);
+        self.db_pool_connections
+            .with_label_values(&["idle"])
+            .set(idle);
+
+        let escalations_pending = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM governance_escalations WHERE status = 'PENDING'",
+        )
+        .fetch_one(pool)
+        .await?;
+        self.escalations_pending.set(escalations_pending);
+
+        let net_balance = sqlx::query_scalar::<_, Decimal>(
+            "SELECT COALESCE(SUM(debit) - SUM(credit), 0) FROM journals",
+        )
+        .fetch_one(pool)
+        .await?;
+        self.journal_balance_deviation
+            .set(net_balance.to_f64().unwrap_or(f64::NAN));
+
+        Ok(())
+    }
+
+    pub fn render(&self) -> String {
+        TextEncoder::new()
+            .encode_to_string(&self.registry.gather())
+            .unwrap_or_else(|err| {
+                warn!("failed to encode metrics: {err}");
+                String::new()
+            })
+    }
+
+    fn observe_request(&self, path: &str, method: &str, status: u16, latency_secs: f64) {
+        let status = status.to_string();
+        let labels = [path, method, status.as_str()];
+        self.requests_total.with_label_values(&labels).inc();
+        self.request_duration
+            .with_label_values(&labels)
+            .observe(latency_secs);
+    }
+}
+
+pub async fn track_metrics(
+    State(metrics): State<Metrics>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = request.method().to_string();
+
+    let response = next.run(request).await;
+
+    metrics.observe_request(
+        &path,
+        &method,
+        response.status().as_u16(),
+        started.elapsed().as_secs_f64(),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus::{CounterVec, IntCounterVec};
+
+    use super::*;
+
+    #[test]
+    fn render_exposes_typed_request_series() {
+        let metrics = Metrics::default();
+        metrics.observe_request("/orders", "POST", 201, 0.02);
+        metrics.observe_request("/orders", "POST", 201, 3.0);
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("# TYPE http_requests_total counter"));
+        assert!(
+            rendered
+                .contains(r#"http_requests_total{method="POST",path="/orders",status="201"} 2"#)
+        );
+        assert!(rendered.contains("# TYPE zavora_request_duration_seconds histogram"));
+        assert!(rendered.contains(
+            r#"zavora_request_duration_seconds_bucket{method="POST",path="/orders",status="201",le="0.025"} 1"#
+        ));
+        assert!(rendered.contains(
+            r#"zavora_request_duration_seconds_count{method="POST",path="/orders",status="201"} 2"#
+        ));
+        assert!(rendered.contains("# TYPE zavora_escalations_pending gauge"));
+        assert!(rendered.contains("# TYPE zavora_journal_balance_deviation gauge"));
+    }
+
+    #[test]
+    fn registered_collectors_render_and_duplicates_are_rejected() {
+        let metrics = Metrics::default();
+        let orders = metrics
+            .register(
+                IntCounterVec::new(Opts::new("orders_created_total", "Orders."), &["type"])
+                    .unwrap(),
+            )
+            .unwrap();
+        orders.with_label_values(&["SERVICE"]).inc();
+
+        assert!(
+            metrics
+                .render()
+                .contains(r#"orders_created_total{type="SERVICE"} 1"#)
+        );
+        assert!(
+            metrics
+                .register(
+                    CounterVec::new(Opts::new("orders_created_total", "Again."), &["type"])
+                        .unwrap()
+                )
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,364 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{redis_bus::RedisBus, shutdown::Shutdown};
+
+pub const DEFAULT_MAX_PUBLISH_ATTEMPTS: u32 = 5;
+pub const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_secs(5);
+pub const DEFAULT_RETRY_MAX_DELAY: Duration = Duration::from_secs(900);
+const RELAY_BATCH_SIZE: i64 = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutboxRetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for OutboxRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_PUBLISH_ATTEMPTS,
+            base_delay: DEFAULT_RETRY_BASE_DELAY,
+            max_delay: DEFAULT_RETRY_MAX_DELAY,
+        }
+    }
+}
+
+impl OutboxRetryPolicy {
+    pub fn is_exhausted(&self, attempts: u32) -> bool {
+        attempts >= self.max_attempts
+    }
+
+    pub fn retry_delay(&self, attempts: u32) -> Duration {
+        let doublings = attempts.saturating_sub(1).min(31);
+        self.base_delay
+            .saturating_mul(1 << doublings)
+            .min(self.max_delay)
+    }
+}
+
+pub async fn enqueue_event<T: Serialize>(
+    tx: &mut Transaction<'_, Postgres>,
+    topic: &str,
+    aggregate_id: Uuid,
+    payload: &T,
+) -> Result<Uuid> {
+    let id = Uuid::new_v4();
+    let payload_json = serde_json::to_value(payload)?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO event_outbox (id, topic, aggregate_id, payload_json, created_at, next_attempt_at)
+        VALUES ($1, $2, $3, $4, $5, $5)
+        "#,
+    )
+    .bind(id)
+    .bind(topic)
+    .bind(aggregate_id)
+    .bind(payload_json)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(id)
+}
+
+pub async fn publish_pending(
+    pool: &PgPool,
+    redis: &RedisBus,
+    policy: &OutboxRetryPolicy,
+) -> Result<usize> {
+    let mut tx = pool.begin().await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT id, topic, payload_json, attempts
+        FROM event_outbox
+        WHERE published_at IS NULL
+          AND next_attempt_at <= NOW()
+        ORDER BY created_at
+        LIMIT $1
+        FOR UPDATE SKIP LOCKED
+        "#,
+    )
+    .bind(RELAY_BATCH_SIZE)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut published = 0;
+    for row in rows {
+        let id: Uuid = row.try_get("id")?;
+        let topic: String = row.try_get("topic")?;
+        let payload_json: Value = row.try_get("payload_json")?;
+        let attempts: i32 = row.try_get("attempts")?;
+        let attempts = u32::try_from(attempts)
+            .unwrap_or_default()
+            .saturating_add(1);
+
+        match redis.publish_json(&topic, &payload_json).await {
+            Ok(()) => {
+                sqlx::query(
+                    "UPDATE event_outbox SET published_at = $2, attempts = attempts + 1, last_error = NULL WHERE id = $1",
+                )
+                .bind(id)
+                .bind(Utc::now())
+                .execute(&mut *tx)
+                .await?;
+                published += 1;
+            }
+            Err(err) if policy.is_exhausted(attempts) => {
+                error!(%id, %topic, attempts, "dead-lettering outbox event: {err}");
+                sqlx::query(
+                    r#"
+                    INSERT INTO dead_letter_events (
+                        id, topic, aggregate_id, payload_json, attempts, last_error, created_at, dead_lettered_at
+                    )
+                    SELECT id, topic, aggregate_id, payload_json, attempts + 1, $2, created_at, $3
+                    FROM event_outbox
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(id)
+                .bind(err.to_string())
+                .bind(Utc::now())
+                .execute(&mut *tx)
+                .await?;
+
+                sqlx::query("DELETE FROM event_outbox WHERE id = $1")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            Err(err) => {
+                warn!(%id, %topic, attempts, "failed to publish outbox event: {err}");
+                let delay = chrono::Duration::from_std(policy.retry_delay(attempts))?;
+                sqlx::query(
+                    r#"
+                    UPDATE event_outbox
+                    SET attempts = attempts + 1,
+                        last_error = $2,
+                        next_attempt_at = $3
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(id)
+                .bind(err.to_string())
+                .bind(Utc::now() + delay)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+    }
+
+    tx.commit().await?;
+    Ok(published)
+}
+
+pub async fn drain_outbox(
+    pool: &PgPool,
+    redis: &RedisBus,
+    policy: &OutboxRetryPolicy,
+) -> Result<usize> {
+    let mut published = 0;
+    loop {
+        let batch = publish_pending(pool, redis, policy).await?;
+        if batch == 0 {
+            return Ok(published);
+        }
+        published += batch;
+    }
+}
+
+pub async fn run_outbox_relay(
+    pool: PgPool,
+    redis: RedisBus,
+    interval: Duration,
+    policy: OutboxRetryPolicy,
+    shutdown: Shutdown,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            () = shutdown.triggered() => break,
+        }
+        if let Err(err) = publish_pending(&pool, &redis, &policy).await {
+            error!("outbox relay pass failed: {err}");
+        }
+    }
+
+    match drain_outbox(&pool, &redis, &policy).await {
+        Ok(published) => info!(published, "outbox relay flushed before shutdown"),
+        Err(err) => error!("final outbox flush failed: {err}"),
+    }
+}
+
+pub async fn dead_letter_count(pool: &PgPool) -> Result<i64> {
+    let count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM dead_letter_events WHERE discarded_at IS NULL",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing::{FakeRedis, TestDatabase};
+
+    #[test]
+    fn default_backoff_doubles_until_the_fifth_attempt_dead_letters() {
+        let policy = OutboxRetryPolicy::default();
+        let schedule: Vec<u64> = (1..policy.max_attempts)
+            .map(|attempts| policy.retry_delay(attempts).as_secs())
+            .collect();
+
+        assert_eq!(schedule, [5, 10, 20, 40]);
+        let until_dead_letter: u64 = schedule.iter().sum();
+        assert!(until_dead_letter >= 60, "{until_dead_letter}s");
+        assert!(!policy.is_exhausted(policy.max_attempts - 1));
+        assert!(policy.is_exhausted(policy.max_attempts));
+    }
+
+    #[test]
+    fn backoff_is_capped_for_large_attempt_counts() {
+        let policy = OutboxRetryPolicy {
+            max_attempts: u32::MAX,
+            base_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(60),
+        };
+
+        assert_eq!(policy.retry_delay(1), Duration::from_secs(2));
+        assert_eq!(policy.retry_delay(5), Duration::from_secs(32));
+        assert_eq!(policy.retry_delay(6), Duration::from_secs(60));
+        assert_eq!(policy.retry_delay(u32::MAX), Duration::from_secs(60));
+    }
+
+    async fn outbox_row(pool: &PgPool, id: Uuid) -> (i32, Option<String>, bool, bool) {
+        let row = sqlx::query(
+            r#"
+            SELECT attempts, last_error, published_at IS NOT NULL AS published,
+                   next_attempt_at > NOW() AS backing_off
+            FROM event_outbox
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .expect("outbox row");
+        (
+            row.get("attempts"),
+            row.get("last_error"),
+            row.get("published"),
+            row.get("backing_off"),
+        )
+    }
+
+    #[tokio::test]
+    async fn failed_publishes_back_off_and_are_marked_published_on_retry() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let policy = OutboxRetryPolicy::default();
+        let mut tx = db.pool.begin().await.unwrap();
+        let id = enqueue_event(&mut tx, "orders.created", Uuid::new_v4(), &json!({"n": 1}))
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let unreachable = RedisBus::connect("redis://127.0.0.1:1/").unwrap();
+        assert_eq!(
+            publish_pending(&db.pool, &unreachable, &policy)
+                .await
+                .unwrap(),
+            0
+        );
+        let (attempts, last_error, published, backing_off) = outbox_row(&db.pool, id).await;
+        assert_eq!(attempts, 1);
+        assert!(last_error.is_some());
+        assert!(!published);
+        assert!(backing_off);
+
+        // Still backing off, so a healthy relay leaves the row alone for now.
+        let (_server, url) = FakeRedis::start().await;
+        let redis = RedisBus::connect(&url).unwrap();
+        assert_eq!(publish_pending(&db.pool, &redis, &policy).await.unwrap(), 0);
+        assert_eq!(outbox_row(&db.pool, id).await.0, 1);
+
+        sqlx::query("UPDATE event_outbox SET next_attempt_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(publish_pending(&db.pool, &redis, &policy).await.unwrap(), 1);
+        assert_eq!(outbox_row(&db.pool, id).await, (2, None, true, false));
+        assert_eq!(publish_pending(&db.pool, &redis, &policy).await.unwrap(), 0);
+        assert_eq!(dead_letter_count(&db.pool).await.unwrap(), 0);
+
+        db.drop().await;
+    }
+
+    #[tokio::test]
+    async fn fifth_failed_publish_moves_the_event_to_dead_letters() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let policy = OutboxRetryPolicy::default();
+        let aggregate_id = Uuid::new_v4();
+        let mut tx = db.pool.begin().await.unwrap();
+        let id = enqueue_event(&mut tx, "orders.created", aggregate_id, &json!({"n": 1}))
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let unreachable = RedisBus::connect("redis://127.0.0.1:1/").unwrap();
+        for attempt in 1..=policy.max_attempts {
+            sqlx::query("UPDATE event_outbox SET next_attempt_at = NOW() WHERE id = $1")
+                .bind(id)
+                .execute(&db.pool)
+                .await
+                .unwrap();
+            publish_pending(&db.pool, &unreachable, &policy)
+                .await
+                .unwrap();
+            if attempt < policy.max_attempts {
+                assert_eq!(outbox_row(&db.pool, id).await.0, attempt as i32);
+                assert_eq!(dead_letter_count(&db.pool).await.unwrap(), 0);
+            }
+        }
+
+        let outbox_rows: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM event_outbox WHERE id = $1")
+                .bind(id)
+                .fetch_one(&db.pool)
+                .await
+                .unwrap();
+        assert_eq!(outbox_rows, 0);
+        let dead_letter = sqlx::query(
+            "SELECT aggregate_id, attempts, last_error FROM dead_letter_events WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_one(&db.pool)
+        .await
+        .expect("dead letter row");
+        assert_eq!(dead_letter.get::<Uuid, _>("aggregate_id"), aggregate_id);
+        assert_eq!(dead_letter.get::<i32, _>("attempts"), 5);
+        assert!(dead_letter.get::<Option<String>, _>("last_error").is_some());
+        assert_eq!(dead_letter_count(&db.pool).await.unwrap(), 1);
+
+        db.drop().await;
+    }
+}
@@ -0,0 +1,60 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+struct Bucket {
+    tokens: f64,
+    refreshed_at: Instant,
+}
+
+#[derive(Clone)]
+pub struct AgentRateLimiter {
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+    rate_per_sec: f64,
+    burst: f64,
+}
+
+impl AgentRateLimiter {
+    pub fn new(rate_per_sec: f64, burst: u32) -> Self {
+        Self {
+            buckets: Arc::default(),
+            rate_per_sec,
+            burst: f64::from(burst.max(1)),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.rate_per_sec > 0.0
+    }
+
+    pub fn check(&self, agent_id: &str) -> Result<(), Duration> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let bucket = buckets.entry(agent_id.to_string()).or_insert(Bucket {
+            tokens: self.burst,
+            refreshed_at: now,
+        });
+
+        let elapsed = now.duration_since(bucket.refreshed_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate_per_sec).min(self.burst);
+        bucket.refreshed_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.rate_per_sec,
+            ))
+        }
+    }
+}
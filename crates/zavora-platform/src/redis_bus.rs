@@ -1,6 +1,12 @@
+use std::time::Duration;
+
 use anyhow::Result;
-use redis::{AsyncCommands, Client};
-use serde::Serialize;
+use futures_util::{Stream, StreamExt, stream};
+use redis::{AsyncCommands, Client, aio::PubSubStream};
+use serde::{Serialize, de::DeserializeOwned};
+use tracing::{info, warn};
+
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
 
 #[derive(Clone)]
 pub struct RedisBus {
@@ -17,10 +23,217 @@ impl RedisBus {
         &self.client
     }
 
+    pub async fn ping(&self) -> Result<()> {
+        let mut connection = self.client.get_multiplexed_async_connection().await?;
+        let _: String = redis::cmd("PING").query_async(&mut connection).await?;
+        Ok(())
+    }
+
     pub async fn publish_json<T: Serialize>(&self, channel: &str, payload: &T) -> Result<()> {
         let mut connection = self.client.get_multiplexed_async_connection().await?;
         let serialized = serde_json::to_string(payload)?;
         let _: i64 = connection.publish(channel, serialized).await?;
         Ok(())
     }
+
+    pub fn subscribe_json<T>(&self, channel: &str) -> impl Stream<Item = Result<T>> + Send + use<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        subscription_stream(Subscription {
+            client: self.client.clone(),
+            channel: channel.to_string(),
+            messages: None,
+            failures: 0,
+        })
+    }
+
+    /// Like [`RedisBus::subscribe_json`], but returns only once Redis has acknowledged the
+    /// `SUBSCRIBE`, so nothing published afterwards can be missed.
+    pub async fn subscribe_json_now<T>(
+        &self,
+        channel: &str,
+    ) -> Result<impl Stream<Item = Result<T>> + Send + use<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let messages = open_subscription(&self.client, channel).await?;
+        info!("subscribed to {channel}");
+        Ok(subscription_stream(Subscription {
+            client: self.client.clone(),
+            channel: channel.to_string(),
+            messages: Some(messages),
+            failures: 0,
+        }))
+    }
+}
+
+fn subscription_stream<T>(state: Subscription) -> impl Stream<Item = Result<T>> + Send
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if state.messages.is_none() {
+                if state.failures > 0 {
+                    tokio::time::sleep(reconnect_delay(state.failures)).await;
+                }
+
+                match open_subscription(&state.client, &state.channel).await {
+                    Ok(messages) => {
+                        info!("subscribed to {}", state.channel);
+                        state.messages = Some(messages);
+                        state.failures = 0;
+                    }
+                    Err(err) => {
+                        warn!("failed to subscribe to {}: {err}", state.channel);
+                        state.failures += 1;
+                        continue;
+                    }
+                }
+            }
+
+            let next = match state.messages.as_mut() {
+                Some(messages) => messages.next().await,
+                None => None,
+            };
+
+            match next {
+                Some(msg) => {
+                    let item = msg
+                        .get_payload::<String>()
+                        .map_err(anyhow::Error::from)
+                        .and_then(|payload| {
+                            serde_json::from_str::<T>(&payload).map_err(anyhow::Error::from)
+                        });
+                    return Some((item, state));
+                }
+                None => {
+                    warn!("subscription to {} dropped; reconnecting", state.channel);
+                    state.messages = None;
+                    state.failures += 1;
+                }
+            }
+        }
+    })
+}
+
+struct Subscription {
+    client: Client,
+    channel: String,
+    messages: Option<PubSubStream>,
+    failures: u32,
+}
+
+async fn open_subscription(client: &Client, channel: &str) -> Result<PubSubStream> {
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(channel).await?;
+    Ok(pubsub.into_on_message())
+}
+
+fn reconnect_delay(failures: u32) -> Duration {
+    Duration::from_millis(250)
+        .saturating_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+        .min(MAX_RECONNECT_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{contracts::OrderCreatedEvent, testing::FakeRedis};
+
+    fn order_event() -> OrderCreatedEvent {
+        OrderCreatedEvent {
+            order_id: Uuid::new_v4(),
+            traceparent: None,
+            tracestate: None,
+            correlation_id: Some("corr-1".to_string()),
+        }
+    }
+
+    async fn next_event(
+        events: &mut (impl Stream<Item = Result<OrderCreatedEvent>> + Unpin),
+    ) -> OrderCreatedEvent {
+        tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .expect("event before timeout")
+            .expect("stream stays open")
+            .expect("event decodes")
+    }
+
+    #[tokio::test]
+    async fn subscribers_receive_published_order_events() {
+        let (server, url) = FakeRedis::start().await;
+        let bus = RedisBus::connect(&url).unwrap();
+        let mut events = Box::pin(bus.subscribe_json::<OrderCreatedEvent>("orders.created"));
+        let receiving = tokio::spawn(async move { next_event(&mut events).await });
+        server.wait_for_subscriber("orders.created").await;
+
+        let published = order_event();
+        bus.publish_json("orders.created", &published)
+            .await
+            .unwrap();
+
+        let received = receiving.await.unwrap();
+        assert_eq!(received.order_id, published.order_id);
+        assert_eq!(received.correlation_id.as_deref(), Some("corr-1"));
+    }
+
+    #[tokio::test]
+    async fn subscriptions_reconnect_after_the_connection_drops() {
+        let (server, url) = FakeRedis::start().await;
+        let bus = RedisBus::connect(&url).unwrap();
+        let mut events = Box::pin(bus.subscribe_json::<OrderCreatedEvent>("orders.created"));
+        let (forward, mut received) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                if forward.send(next_event(&mut events).await).is_err() {
+                    break;
+                }
+            }
+        });
+        server.wait_for_subscriber("orders.created").await;
+
+        server.drop_connections();
+        server.wait_for_subscriber("orders.created").await;
+        let published = order_event();
+        bus.publish_json("orders.created", &published)
+            .await
+            .unwrap();
+
+        let received = tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(received.order_id, published.order_id);
+    }
+
+    #[tokio::test]
+    async fn eager_subscriptions_receive_events_published_right_after_they_open() {
+        let (_server, url) = FakeRedis::start().await;
+        let bus = RedisBus::connect(&url).unwrap();
+        let mut events = Box::pin(
+            bus.subscribe_json_now::<OrderCreatedEvent>("orders.created")
+                .await
+                .unwrap(),
+        );
+
+        let published = order_event();
+        bus.publish_json("orders.created", &published)
+            .await
+            .unwrap();
+
+        assert_eq!(next_event(&mut events).await.order_id, published.order_id);
+    }
+
+    #[test]
+    fn reconnect_delay_backs_off_up_to_the_cap() {
+        assert_eq!(reconnect_delay(1), Duration::from_millis(250));
+        assert_eq!(reconnect_delay(2), Duration::from_millis(500));
+        assert_eq!(reconnect_delay(4), Duration::from_secs(2));
+        assert_eq!(reconnect_delay(20), MAX_RECONNECT_DELAY);
+    }
 }
@@ -0,0 +1,168 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+pub const AGENT_ID_HEADER: HeaderName = HeaderName::from_static("x-agent-id");
+pub const CORRELATION_ID_HEADER: HeaderName = HeaderName::from_static("x-correlation-id");
+const RECENT_REQUESTS_CAPACITY: usize = 1000;
+const MAX_CORRELATION_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_CONTEXT: Arc<RequestContext>;
+}
+
+struct RequestContext {
+    request_id: Uuid,
+    correlation_id: String,
+    agent_id: Mutex<Option<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestLogEntry {
+    pub request_id: Uuid,
+    pub correlation_id: String,
+    pub method: String,
+    pub path: String,
+    pub agent_id: Option<String>,
+    pub status: u16,
+    pub latency_ms: u64,
+    pub completed_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Default)]
+pub struct RequestLog {
+    entries: Arc<Mutex<VecDeque<RequestLogEntry>>>,
+}
+
+impl RequestLog {
+    pub fn recent(&self, limit: usize) -> Vec<RequestLogEntry> {
+        let entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        entries.iter().rev().take(limit).cloned().collect()
+    }
+
+    fn push(&self, entry: RequestLogEntry) {
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if entries.len() == RECENT_REQUESTS_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+pub fn current_request_id() -> Option<Uuid> {
+    REQUEST_CONTEXT.try_with(|context| context.request_id).ok()
+}
+
+pub fn current_correlation_id() -> Option<String> {
+    REQUEST_CONTEXT
+        .try_with(|context| context.correlation_id.clone())
+        .ok()
+}
+
+pub fn set_request_agent_id(agent_id: &str) {
+    let _ = REQUEST_CONTEXT.try_with(|context| {
+        if let Ok(mut current) = context.agent_id.lock() {
+            *current = Some(agent_id.to_string());
+        }
+    });
+}
+
+pub async fn log_requests(State(log): State<RequestLog>, request: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Uuid::parse_str(value.trim()).ok())
+        .unwrap_or_else(Uuid::new_v4);
+    let correlation_id = request
+        .headers()
+        .get(&CORRELATION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| {
+            !value.is_empty()
+                && value.len() <= MAX_CORRELATION_ID_LEN
+                && value.chars().all(|ch| ch.is_ascii_graphic())
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| request_id.to_string());
+    let agent_id = request
+        .headers()
+        .get(&AGENT_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+
+    let span = tracing::Span::current();
+    span.record("request_id", tracing::field::display(request_id));
+    span.record("correlation_id", correlation_id.as_str());
+
+    let context = Arc::new(RequestContext {
+        request_id,
+        correlation_id: correlation_id.clone(),
+        agent_id: Mutex::new(agent_id),
+    });
+    let mut response = REQUEST_CONTEXT
+        .scope(context.clone(), next.run(request))
+        .await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id.to_string()) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&correlation_id) {
+        response.headers_mut().insert(CORRELATION_ID_HEADER, value);
+    }
+
+    let status = response.status();
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let agent_id = context
+        .agent_id
+        .lock()
+        .ok()
+        .and_then(|current| current.clone());
+    let agent_label = agent_id.as_deref().unwrap_or("-");
+
+    if status.is_server_error() {
+        error!(%method, %path, agent_id = agent_label, %request_id, %correlation_id, status = status.as_u16(), latency_ms, "request failed");
+    } else if status.is_client_error() {
+        warn!(%method, %path, agent_id = agent_label, %request_id, %correlation_id, status = status.as_u16(), latency_ms, "request rejected");
+    } else {
+        info!(%method, %path, agent_id = agent_label, %request_id, %correlation_id, status = status.as_u16(), latency_ms, "request completed");
+    }
+
+    log.push(RequestLogEntry {
+        request_id,
+        correlation_id,
+        method,
+        path,
+        agent_id,
+        status: status.as_u16(),
+        latency_ms,
+        completed_at: Utc::now(),
+    });
+
+    response
+}
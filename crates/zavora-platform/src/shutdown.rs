@@ -0,0 +1,104 @@
+use std::{future::IntoFuture, sync::Arc, time::Duration};
+
+use anyhow::Result;
+use axum::Router;
+use tokio::{net::TcpListener, sync::watch};
+use tracing::{error, info, warn};
+
+#[derive(Clone)]
+pub struct Shutdown {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self {
+            sender: Arc::new(watch::channel(false).0),
+        }
+    }
+}
+
+impl Shutdown {
+    pub fn is_draining(&self) -> bool {
+        *self.sender.borrow()
+    }
+
+    pub fn trigger(&self) {
+        self.sender.send_replace(true);
+    }
+
+    pub async fn triggered(&self) {
+        let mut receiver = self.sender.subscribe();
+        let _ = receiver.wait_for(|draining| *draining).await;
+    }
+
+    pub async fn listen_for_signals(self) {
+        wait_for_signal().await;
+        info!("shutdown signal received, draining in-flight requests");
+        self.trigger();
+    }
+}
+
+pub async fn serve_with_graceful_shutdown(
+    listener: TcpListener,
+    router: Router,
+    shutdown: Shutdown,
+    drain_timeout: Duration,
+) -> Result<()> {
+    let graceful = {
+        let shutdown = shutdown.clone();
+        async move { shutdown.triggered().await }
+    };
+    let mut server = tokio::spawn(
+        axum::serve(listener, router)
+            .with_graceful_shutdown(graceful)
+            .into_future(),
+    );
+
+    tokio::select! {
+        result = &mut server => return Ok(result??),
+        () = shutdown.triggered() => {}
+    }
+
+    match tokio::time::timeout(drain_timeout, &mut server).await {
+        Ok(result) => result??,
+        Err(_) => {
+            warn!(
+                timeout_secs = drain_timeout.as_secs(),
+                "shutdown timeout elapsed with requests still in flight"
+            );
+            server.abort();
+        }
+    }
+
+    Ok(())
+}
+
+async fn wait_for_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            error!("failed to listen for SIGINT: {err}");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                error!("failed to listen for SIGTERM: {err}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
+}
@@ -0,0 +1,216 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use anyhow::Result;
+use http::{Request, Response};
+use tower_http::trace::{DefaultOnResponse, MakeSpan, OnResponse};
+use tracing::{Event, Span, Subscriber, field::Empty, span};
+use tracing_subscriber::{
+    EnvFilter, Layer, Registry,
+    filter::filter_fn,
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    util::SubscriberInitExt,
+};
+
+const DB_QUERY_TARGET: &str = "sqlx::query";
+
+#[derive(Debug, Clone, Default)]
+pub struct TraceContextHeaders {
+    pub traceparent: Option<String>,
+    pub tracestate: Option<String>,
+}
+
+pub struct TelemetryGuard {
+    #[cfg(feature = "telemetry")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "telemetry")]
+        if let Some(provider) = self.provider.take()
+            && let Err(err) = provider.shutdown()
+        {
+            tracing::error!("failed to flush trace exporter: {err}");
+        }
+    }
+}
+
+pub fn init_tracing(
+    service_name: &'static str,
+    default_filter: &str,
+    otlp_endpoint: Option<&str>,
+) -> Result<TelemetryGuard> {
+    let env_filter =
+        || EnvFilter::new(std::env::var("RUST_LOG").unwrap_or_else(|_| default_filter.to_string()));
+
+    let registry = tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_filter(env_filter()))
+        .with(DbQueryCountLayer.with_filter(filter_fn(|metadata| {
+            metadata.is_span() || metadata.target() == DB_QUERY_TARGET
+        })));
+
+    #[cfg(feature = "telemetry")]
+    {
+        use opentelemetry::trace::TracerProvider as _;
+        use opentelemetry_otlp::WithExportConfig;
+
+        let Some(endpoint) = otlp_endpoint else {
+            registry.init();
+            return Ok(TelemetryGuard { provider: None });
+        };
+
+        opentelemetry::global::set_text_map_propagator(
+            opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+        );
+
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_endpoint(endpoint)
+            .build()?;
+        let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(
+                opentelemetry_sdk::Resource::builder()
+                    .with_service_name(service_name)
+                    .build(),
+            )
+            .build();
+        let tracer = provider.tracer(service_name);
+
+        registry
+            .with(
+                tracing_opentelemetry::layer()
+                    .with_tracer(tracer)
+                    .with_filter(env_filter()),
+            )
+            .init();
+
+        Ok(TelemetryGuard {
+            provider: Some(provider),
+        })
+    }
+
+    #[cfg(not(feature = "telemetry"))]
+    {
+        let _ = (service_name, otlp_endpoint);
+        registry.init();
+        Ok(TelemetryGuard {})
+    }
+}
+
+pub fn current_trace_context() -> TraceContextHeaders {
+    #[cfg(feature = "telemetry")]
+    {
+        use std::collections::HashMap;
+
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let context = Span::current().context();
+        let mut carrier = HashMap::new();
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&context, &mut carrier);
+        });
+
+        TraceContextHeaders {
+            traceparent: carrier.remove("traceparent"),
+            tracestate: carrier
+                .remove("tracestate")
+                .filter(|value| !value.is_empty()),
+        }
+    }
+
+    #[cfg(not(feature = "telemetry"))]
+    {
+        TraceContextHeaders::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestSpan;
+
+impl<B> MakeSpan<B> for RequestSpan {
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        tracing::info_span!(
+            "http.request",
+            otel.name = %format!("{} {}", request.method(), request.uri().path()),
+            http.method = %request.method(),
+            http.target = %request.uri(),
+            http.status_code = Empty,
+            request_id = Empty,
+            correlation_id = Empty,
+            agent_id = Empty,
+            action_type = Empty,
+            order_id = Empty,
+            db_query_count = Empty,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RecordRequestOutcome {
+    inner: DefaultOnResponse,
+}
+
+impl<B> OnResponse<B> for RecordRequestOutcome {
+    fn on_response(self, response: &Response<B>, latency: Duration, span: &Span) {
+        span.record("http.status_code", response.status().as_u16());
+        if let Some(count) = db_query_count(span) {
+            span.record("db_query_count", count);
+        }
+
+        self.inner.on_response(response, latency, span);
+    }
+}
+
+struct DbQueryCount(AtomicU64);
+
+struct DbQueryCountLayer;
+
+impl<S> Layer<S> for DbQueryCountLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if attrs.metadata().fields().field("db_query_count").is_none() {
+            return;
+        }
+
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut()
+                .insert(DbQueryCount(AtomicU64::new(0)));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if event.metadata().target() != DB_QUERY_TARGET {
+            return;
+        }
+
+        let Some(scope) = ctx.event_scope(event) else {
+            return;
+        };
+
+        for span in scope {
+            if let Some(counter) = span.extensions().get::<DbQueryCount>() {
+                counter.0.fetch_add(1, Ordering::Relaxed);
+                break;
+            }
+        }
+    }
+}
+
+fn db_query_count(span: &Span) -> Option<u64> {
+    span.with_subscriber(|(id, dispatch)| {
+        let registry = dispatch.downcast_ref::<Registry>()?;
+        let span = registry.span(id)?;
+        let extensions = span.extensions();
+        extensions
+            .get::<DbQueryCount>()
+            .map(|counter| counter.0.load(Ordering::Relaxed))
+    })
+    .flatten()
+}
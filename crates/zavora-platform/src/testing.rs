@@ -0,0 +1,217 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use sqlx::{
+    ConnectOptions, PgPool,
+    postgres::{PgConnectOptions, PgPoolOptions},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+    task::JoinHandle,
+};
+use uuid::Uuid;
+
+pub const TEST_DATABASE_URL_VAR: &str = "ZAVORA_TEST_DATABASE_URL";
+const SCHEMA_SQL: &str = include_str!("../../../docker/postgres/init/001_schema.sql");
+
+pub struct TestDatabase {
+    admin: PgPool,
+    name: String,
+    options: PgConnectOptions,
+    pub pool: PgPool,
+}
+
+impl TestDatabase {
+    pub async fn create() -> Option<Self> {
+        let Ok(url) = std::env::var(TEST_DATABASE_URL_VAR) else {
+            eprintln!("skipping database test: {TEST_DATABASE_URL_VAR} is not set");
+            return None;
+        };
+
+        let admin = PgPoolOptions::new()
+            .max_connections(1)
+            .connect(&url)
+            .await
+            .expect("connect to the test database server");
+        let name = format!("zavora_test_{}", Uuid::new_v4().simple());
+        sqlx::query(&format!("CREATE DATABASE {name}"))
+            .execute(&admin)
+            .await
+            .expect("create test database");
+
+        let options = url
+            .parse::<PgConnectOptions>()
+            .expect("valid test database url")
+            .database(&name);
+        let pool = PgPoolOptions::new()
+            .max_connections(4)
+            .connect_with(options.clone())
+            .await
+            .expect("connect to test database");
+        sqlx::raw_sql(SCHEMA_SQL)
+            .execute(&pool)
+            .await
+            .expect("apply schema to test database");
+
+        Some(Self {
+            admin,
+            name,
+            options,
+            pool,
+        })
+    }
+
+    pub fn url(&self) -> String {
+        self.options.to_url_lossy().to_string()
+    }
+
+    pub async fn drop(self) {
+        self.pool.close().await;
+        sqlx::query(&format!(
+            "DROP DATABASE IF EXISTS {} WITH (FORCE)",
+            self.name
+        ))
+        .execute(&self.admin)
+        .await
+        .expect("drop test database");
+    }
+}
+
+type Subscribers = HashMap<String, Vec<mpsc::UnboundedSender<Vec<u8>>>>;
+
+/// Speaks just enough RESP2 for `SUBSCRIBE`, `PUBLISH` and connection setup.
+#[derive(Clone, Default)]
+pub struct FakeRedis {
+    subscribers: Arc<Mutex<Subscribers>>,
+    connections: Arc<Mutex<Vec<JoinHandle<()>>>>,
+}
+
+impl FakeRedis {
+    pub async fn start() -> (Self, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("redis://{}/", listener.local_addr().unwrap());
+        let server = Self::default();
+        let accepting = server.clone();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let connection = tokio::spawn(accepting.clone().serve(socket));
+                accepting.connections.lock().unwrap().push(connection);
+            }
+        });
+        (server, url)
+    }
+
+    fn subscriber_count(&self, channel: &str) -> usize {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        let senders = subscribers.entry(channel.to_string()).or_default();
+        senders.retain(|sender| !sender.is_closed());
+        senders.len()
+    }
+
+    pub async fn wait_for_subscriber(&self, channel: &str) {
+        for _ in 0..200 {
+            if self.subscriber_count(channel) > 0 {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("nobody subscribed to {channel}");
+    }
+
+    pub fn drop_connections(&self) {
+        self.subscribers.lock().unwrap().clear();
+        for connection in self.connections.lock().unwrap().drain(..) {
+            connection.abort();
+        }
+    }
+
+    async fn serve(self, socket: TcpStream) {
+        let (reader, mut writer) = socket.into_split();
+        let mut reader = BufReader::new(reader);
+        let (outgoing, mut queued) = mpsc::unbounded_channel::<Vec<u8>>();
+        let writing = tokio::spawn(async move {
+            while let Some(frame) = queued.recv().await {
+                if writer.write_all(&frame).await.is_err() {
+                    break;
+                }
+            }
+        });
+        let _stop_writing = AbortOnDrop(writing);
+
+        while let Some(command) = read_command(&mut reader).await {
+            let name = command[0].to_ascii_uppercase();
+            let reply = match name.as_str() {
+                "SUBSCRIBE" => {
+                    let mut reply = Vec::new();
+                    for (index, channel) in command[1..].iter().enumerate() {
+                        self.subscribers
+                            .lock()
+                            .unwrap()
+                            .entry(channel.clone())
+                            .or_default()
+                            .push(outgoing.clone());
+                        reply.extend(b"*3\r\n");
+                        reply.extend(bulk("subscribe"));
+                        reply.extend(bulk(channel));
+                        reply.extend(format!(":{}\r\n", index + 1).into_bytes());
+                    }
+                    reply
+                }
+                "PUBLISH" => {
+                    let frame = array(&["message", &command[1], &command[2]]);
+                    let mut subscribers = self.subscribers.lock().unwrap();
+                    let senders = subscribers.entry(command[1].clone()).or_default();
+                    senders.retain(|sender| sender.send(frame.clone()).is_ok());
+                    format!(":{}\r\n", senders.len()).into_bytes()
+                }
+                "PING" => b"+PONG\r\n".to_vec(),
+                _ => b"+OK\r\n".to_vec(),
+            };
+            if outgoing.send(reply).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+fn bulk(value: &str) -> Vec<u8> {
+    format!("${}\r\n{value}\r\n", value.len()).into_bytes()
+}
+
+fn array(values: &[&str]) -> Vec<u8> {
+    let mut frame = format!("*{}\r\n", values.len()).into_bytes();
+    for value in values {
+        frame.extend(bulk(value));
+    }
+    frame
+}
+
+async fn read_command<R: AsyncBufReadExt + Unpin>(reader: &mut R) -> Option<Vec<String>> {
+    let mut line = String::new();
+    reader.read_line(&mut line).await.ok()?;
+    let count: usize = line.trim().strip_prefix('*')?.parse().ok()?;
+    let mut parts = Vec::with_capacity(count);
+    for _ in 0..count {
+        line.clear();
+        reader.read_line(&mut line).await.ok()?;
+        let len: usize = line.trim().strip_prefix('$')?.parse().ok()?;
+        let mut value = vec![0; len + 2];
+        reader.read_exact(&mut value).await.ok()?;
+        value.truncate(len);
+        parts.push(String::from_utf8(value).ok()?);
+    }
+    Some(parts)
+}
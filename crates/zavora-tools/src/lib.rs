@@ -1,17 +1,985 @@
+use std::{future::Future, process::Stdio, time::Duration};
+
+use anyhow::Context;
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use hmac::{Hmac, Mac};
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use sha2::Sha256;
+use tokio::{io::AsyncWriteExt, process::Command};
 use uuid::Uuid;
+use zavora_core::{A2AMessage, validate_a2a_message};
+
+const GATEWAY_TOKEN_TTL_SECS: i64 = 300;
 
 #[async_trait]
 pub trait MessagingTool: Send + Sync {
     async fn send_message(&self, recipient: &str, subject: &str, body: &str) -> anyhow::Result<()>;
+
+    async fn send_a2a_message<T>(&self, message: &A2AMessage<T>) -> anyhow::Result<()>
+    where
+        Self: Sized,
+        T: Serialize + Send + Sync,
+    {
+        let body = serde_json::to_string(message)?;
+        self.send_message(
+            &message.recipient_agent_id,
+            &format!("a2a:{}", message.message_id),
+            &body,
+        )
+        .await
+    }
+
+    async fn receive_a2a_messages<T>(
+        &self,
+        recipient_agent_id: &str,
+    ) -> anyhow::Result<Vec<A2AMessage<T>>>
+    where
+        Self: Sized,
+        T: DeserializeOwned + Send,
+    {
+        self.pending_a2a_messages(recipient_agent_id)
+            .await?
+            .iter()
+            .map(|raw| validate_a2a_message(raw).map_err(Into::into))
+            .collect()
+    }
+
+    async fn pending_a2a_messages(&self, _recipient_agent_id: &str) -> anyhow::Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("message to {recipient} failed after {attempts} attempts: {last_error}")]
+pub struct MessageDeliveryError {
+    pub recipient: String,
+    pub attempts: u32,
+    pub last_error: anyhow::Error,
+}
+
+pub struct RetryingMessaging<T: MessagingTool> {
+    pub inner: T,
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl<T: MessagingTool> RetryingMessaging<T> {
+    pub fn new(inner: T, max_attempts: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            inner,
+            max_attempts: max_attempts.max(1),
+            base_delay,
+            max_delay,
+        }
+    }
+
+    async fn with_retries<F, Fut>(&self, recipient: &str, mut send: F) -> anyhow::Result<()>
+    where
+        F: FnMut() -> Fut + Send,
+        Fut: Future<Output = anyhow::Result<()>> + Send,
+    {
+        let max_attempts = self.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match send().await {
+                Ok(()) => return Ok(()),
+                Err(err) if attempt >= max_attempts => {
+                    return Err(MessageDeliveryError {
+                        recipient: recipient.to_string(),
+                        attempts: attempt,
+                        last_error: err,
+                    }
+                    .into());
+                }
+                Err(_) => {
+                    tokio::time::sleep(self.backoff(attempt)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_delay)
+    }
+}
+
+#[async_trait]
+impl<T: MessagingTool> MessagingTool for RetryingMessaging<T> {
+    async fn send_message(&self, recipient: &str, subject: &str, body: &str) -> anyhow::Result<()> {
+        self.with_retries(recipient, || {
+            self.inner.send_message(recipient, subject, body)
+        })
+        .await
+    }
+
+    async fn send_a2a_message<M>(&self, message: &A2AMessage<M>) -> anyhow::Result<()>
+    where
+        M: Serialize + Send + Sync,
+    {
+        self.with_retries(&message.recipient_agent_id, || {
+            self.inner.send_a2a_message(message)
+        })
+        .await
+    }
+
+    async fn pending_a2a_messages(&self, recipient_agent_id: &str) -> anyhow::Result<Vec<String>> {
+        self.inner.pending_a2a_messages(recipient_agent_id).await
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reservation {
+    Reserved { reservation_id: Uuid },
+    InsufficientStock { available: f64 },
 }
 
 #[async_trait]
 pub trait InventoryTool: Send + Sync {
     async fn quantity_available(&self, item_code: &str) -> anyhow::Result<f64>;
+    async fn reserve_quantity(&self, item_code: &str, quantity: f64)
+    -> anyhow::Result<Reservation>;
+}
+
+#[derive(Debug, Clone)]
+pub struct ScoredLead {
+    pub lead_id: Uuid,
+    pub contact_email: String,
+    pub status: String,
+    pub score: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait LeadTool: Send + Sync {
+    async fn open_leads(&self) -> anyhow::Result<Vec<ScoredLead>>;
+    async fn record_follow_up(&self, lead_id: Uuid) -> anyhow::Result<()>;
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomerProfile {
+    pub customer_id: Uuid,
+    pub email: String,
+    pub risk_class: String,
+    pub credit_limit: Option<Decimal>,
+    pub available_credit: Option<Decimal>,
+}
+
+#[async_trait]
+pub trait CustomerTool: Send + Sync {
+    async fn customer_by_email(&self, email: &str) -> anyhow::Result<Option<CustomerProfile>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct OrderLine {
+    pub order_id: Uuid,
+    pub item_code: String,
+    pub quantity: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutOfStockEvent {
+    pub order_id: Uuid,
+    pub item_code: String,
+    pub requested_quantity: f64,
+    pub available_quantity: f64,
+}
+
+#[async_trait]
+pub trait OrderTool: Send + Sync {
+    async fn order_line(&self, order_id: Uuid) -> anyhow::Result<OrderLine>;
+    async fn fulfill_order(&self, order_id: Uuid) -> anyhow::Result<()>;
+    /// Parks the order as `AWAITING_STOCK` and publishes the stockout to `warehouse.stockouts`.
+    async fn mark_awaiting_stock(&self, stockout: &OutOfStockEvent) -> anyhow::Result<()>;
+}
+
+/// Gateway-backed [`OrderTool`] that signs a short-lived bearer token for every call.
+pub struct HttpOrderTool {
+    client: reqwest::Client,
+    base_url: String,
+    agent_id: String,
+    token_secret: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GatewayOrder {
+    order_id: Uuid,
+    item_code: String,
+    quantity: Decimal,
+}
+
+impl HttpOrderTool {
+    pub fn new(
+        base_url: impl Into<String>,
+        agent_id: impl Into<String>,
+        token_secret: impl Into<String>,
+    ) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            agent_id: agent_id.into(),
+            token_secret: token_secret.into(),
+        }
+    }
+
+    fn bearer_token(&self) -> anyhow::Result<String> {
+        let expires_at = Utc::now().timestamp() + GATEWAY_TOKEN_TTL_SECS;
+        let claims = format!("{}.{expires_at}", self.agent_id);
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.token_secret.as_bytes())?;
+        mac.update(claims.as_bytes());
+        Ok(format!(
+            "{claims}.{}",
+            hex::encode(mac.finalize().into_bytes())
+        ))
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> anyhow::Result<reqwest::Response> {
+        let response = request.bearer_auth(self.bearer_token()?).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let url = response.url().clone();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("{url} answered {status}: {body}");
+        }
+        Ok(response)
+    }
+
+    async fn post_transition(
+        &self,
+        order_id: Uuid,
+        action: &str,
+        body: Value,
+    ) -> anyhow::Result<()> {
+        self.send(
+            self.client
+                .post(format!("{}/orders/{order_id}/{action}", self.base_url))
+                .json(&body),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OrderTool for HttpOrderTool {
+    async fn order_line(&self, order_id: Uuid) -> anyhow::Result<OrderLine> {
+        let order: GatewayOrder = self
+            .send(
+                self.client
+                    .get(format!("{}/orders/{order_id}", self.base_url)),
+            )
+            .await?
+            .json()
+            .await?;
+        Ok(OrderLine {
+            order_id: order.order_id,
+            item_code: order.item_code,
+            quantity: order
+                .quantity
+                .to_f64()
+                .context("order quantity is out of range")?,
+        })
+    }
+
+    async fn fulfill_order(&self, order_id: Uuid) -> anyhow::Result<()> {
+        self.post_transition(
+            order_id,
+            "fulfill",
+            serde_json::json!({ "requested_by_agent_id": self.agent_id }),
+        )
+        .await
+    }
+
+    async fn mark_awaiting_stock(&self, stockout: &OutOfStockEvent) -> anyhow::Result<()> {
+        self.post_transition(
+            stockout.order_id,
+            "awaiting-stock",
+            serde_json::json!({
+                "requested_by_agent_id": self.agent_id,
+                "item_code": stockout.item_code,
+                "requested_quantity": stockout.requested_quantity,
+                "available_quantity": stockout.available_quantity,
+            }),
+        )
+        .await
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplierCommitment {
+    pub request_id: Uuid,
+    pub commitment_id: Uuid,
+    pub supplier: String,
+    pub item_code: String,
+    pub quantity: f64,
+    pub total_amount: Decimal,
+    pub currency: String,
 }
 
 #[async_trait]
 pub trait CommitmentTool: Send + Sync {
     async fn create_sales_commitment(&self, customer: &str, quote: &str) -> anyhow::Result<Uuid>;
+    /// Opens a supplier request for a shortage. Calling again with the same `shortage_id`
+    /// returns the existing request instead of opening a second one.
+    async fn create_supplier_commitment(
+        &self,
+        shortage_id: Uuid,
+        supplier: &str,
+        item_code: &str,
+        quantity: f64,
+    ) -> anyhow::Result<Uuid>;
+    async fn create_procurement_obligation(
+        &self,
+        commitment: &SupplierCommitment,
+    ) -> anyhow::Result<Uuid>;
+    async fn create_escalation(&self, action_type: &str, details: &Value) -> anyhow::Result<Uuid>;
+    /// The escalation's current status: `PENDING`, `APPROVED`, `REJECTED` or `FROZEN`.
+    async fn escalation_status(&self, escalation_id: Uuid) -> anyhow::Result<String>;
+}
+
+#[derive(Debug, Clone)]
+pub struct ArAgingItem {
+    pub order_id: Uuid,
+    pub invoice_id: Uuid,
+    pub customer_email: String,
+    pub outstanding: Decimal,
+    pub currency: String,
+    pub age_days: i64,
+    pub collection_stage: Option<CollectionStage>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CollectionStage {
+    Reminded,
+    Escalated,
+}
+
+#[async_trait]
+pub trait ReceivablesTool: Send + Sync {
+    async fn uninvoiced_fulfilled_orders(&self) -> anyhow::Result<Vec<Uuid>>;
+    async fn generate_invoice(&self, order_id: Uuid) -> anyhow::Result<Uuid>;
+    async fn aging_items(&self) -> anyhow::Result<Vec<ArAgingItem>>;
+    async fn escalate_collection(
+        &self,
+        item: &ArAgingItem,
+        action_type: &str,
+    ) -> anyhow::Result<Uuid>;
+    async fn record_collection_stage(
+        &self,
+        invoice_id: Uuid,
+        stage: CollectionStage,
+    ) -> anyhow::Result<()>;
+}
+
+#[derive(Debug, Clone)]
+pub struct TrialBalanceTotals {
+    pub total_debits: Decimal,
+    pub total_credits: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KpiTargetKey {
+    pub business_unit: String,
+    pub mandate: String,
+    pub metric_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodCloseStepOutcome {
+    pub step: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodCloseLog {
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub steps: Vec<PeriodCloseStepOutcome>,
+    pub escalation_id: Option<Uuid>,
+}
+
+#[async_trait]
+pub trait PeriodCloseTool: Send + Sync {
+    async fn trial_balance(&self, as_of: NaiveDate) -> anyhow::Result<TrialBalanceTotals>;
+    async fn run_allocation(
+        &self,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> anyhow::Result<()>;
+    async fn reconciliation_status(
+        &self,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> anyhow::Result<String>;
+    async fn active_kpi_targets(
+        &self,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> anyhow::Result<Vec<KpiTargetKey>>;
+    async fn evaluate_variance(
+        &self,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+        target: &KpiTargetKey,
+    ) -> anyhow::Result<String>;
+    async fn post_due_recurring_journals(
+        &self,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> anyhow::Result<usize>;
+    async fn create_escalation(&self, action_type: &str, details: &Value) -> anyhow::Result<Uuid>;
+    async fn record_period_close(&self, log: &PeriodCloseLog) -> anyhow::Result<Uuid>;
+    /// Whether `period_close_log` already holds a `CLOSED` entry, one where every step passed, for the period.
+    async fn period_closed(
+        &self,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> anyhow::Result<bool>;
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditRequest {
+    pub request_id: Uuid,
+    pub order_id: Uuid,
+}
+
+#[async_trait]
+pub trait AuditTool: Send + Sync {
+    async fn pending_audit_requests(&self) -> anyhow::Result<Vec<AuditRequest>>;
+    async fn order_evidence(&self, order_id: Uuid) -> anyhow::Result<Value>;
+    async fn store_evidence(
+        &self,
+        request: &AuditRequest,
+        evidence_hash: &str,
+        evidence: &Value,
+    ) -> anyhow::Result<Uuid>;
+    async fn complete_audit_request(&self, request_id: Uuid) -> anyhow::Result<()>;
+}
+
+#[async_trait]
+pub trait SkillExecutor: Send + Sync {
+    async fn execute(
+        &self,
+        skill_id: &str,
+        skill_version: &str,
+        input: &Value,
+        test_context: Option<&Value>,
+    ) -> anyhow::Result<Value>;
+}
+
+pub struct CommandSkillExecutor {
+    program: String,
+    timeout: Duration,
+}
+
+impl CommandSkillExecutor {
+    pub fn new(program: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            program: program.into(),
+            timeout,
+        }
+    }
+}
+
+#[async_trait]
+impl SkillExecutor for CommandSkillExecutor {
+    async fn execute(
+        &self,
+        skill_id: &str,
+        skill_version: &str,
+        input: &Value,
+        test_context: Option<&Value>,
+    ) -> anyhow::Result<Value> {
+        let mut child = Command::new(&self.program)
+            .env_clear()
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("failed to start skill executor {}", self.program))?;
+
+        let request = serde_json::to_vec(&serde_json::json!({
+            "skill_id": skill_id,
+            "skill_version": skill_version,
+            "input": input,
+            "test_context": test_context,
+        }))?;
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("skill executor stdin is unavailable"))?;
+        if let Err(err) = stdin.write_all(&request).await
+            && err.kind() != std::io::ErrorKind::BrokenPipe
+        {
+            return Err(err.into());
+        }
+        drop(stdin);
+
+        let output = tokio::time::timeout(self.timeout, child.wait_with_output())
+            .await
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "skill executor timed out after {} seconds",
+                    self.timeout.as_secs()
+                )
+            })??;
+        if !output.status.success() {
+            anyhow::bail!(
+                "skill executor exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        serde_json::from_slice(&output.stdout).context("skill executor output is not valid JSON")
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+pub struct MockSkillExecutor;
+
+#[cfg(any(test, feature = "test-support"))]
+#[async_trait]
+impl SkillExecutor for MockSkillExecutor {
+    async fn execute(
+        &self,
+        _skill_id: &str,
+        _skill_version: &str,
+        input: &Value,
+        test_context: Option<&Value>,
+    ) -> anyhow::Result<Value> {
+        Ok(test_context
+            .and_then(|context| context.get("mock_output"))
+            .cloned()
+            .unwrap_or_else(|| input.clone()))
+    }
+}
+
+#[async_trait]
+pub trait PaymentTool: Send + Sync {
+    async fn initiate_payment(
+        &self,
+        counterparty: &str,
+        amount: Decimal,
+        currency: &str,
+    ) -> anyhow::Result<Uuid>;
+}
+
+#[cfg(any(test, feature = "test-support"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockPayment {
+    pub reference: Uuid,
+    pub counterparty: String,
+    pub amount: Decimal,
+    pub currency: String,
+}
+
+#[cfg(any(test, feature = "test-support"))]
+#[derive(Default)]
+pub struct MockPaymentTool {
+    failure: Option<String>,
+    payments: std::sync::Mutex<Vec<MockPayment>>,
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl MockPaymentTool {
+    pub fn failing(reason: impl Into<String>) -> Self {
+        Self {
+            failure: Some(reason.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn payments(&self) -> Vec<MockPayment> {
+        self.payments.lock().expect("mock payments lock").clone()
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+#[async_trait]
+impl PaymentTool for MockPaymentTool {
+    async fn initiate_payment(
+        &self,
+        counterparty: &str,
+        amount: Decimal,
+        currency: &str,
+    ) -> anyhow::Result<Uuid> {
+        if let Some(reason) = &self.failure {
+            anyhow::bail!("payment to {counterparty} failed: {reason}");
+        }
+        if amount <= Decimal::ZERO {
+            anyhow::bail!("payment amount must be positive");
+        }
+        let reference = Uuid::new_v4();
+        self.payments
+            .lock()
+            .expect("mock payments lock")
+            .push(MockPayment {
+                reference,
+                counterparty: counterparty.to_string(),
+                amount,
+                currency: currency.to_string(),
+            });
+        Ok(reference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct FailingBus {
+        failures_left: Mutex<u32>,
+        delivered: Mutex<Vec<(String, String)>>,
+        attempts: Mutex<u32>,
+    }
+
+    impl FailingBus {
+        fn failing(times: u32) -> Self {
+            Self {
+                failures_left: Mutex::new(times),
+                ..Self::default()
+            }
+        }
+
+        fn attempts(&self) -> u32 {
+            *self.attempts.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl MessagingTool for FailingBus {
+        async fn send_message(
+            &self,
+            recipient: &str,
+            subject: &str,
+            _body: &str,
+        ) -> anyhow::Result<()> {
+            *self.attempts.lock().unwrap() += 1;
+            let mut failures_left = self.failures_left.lock().unwrap();
+            if *failures_left > 0 {
+                *failures_left -= 1;
+                anyhow::bail!("bus unavailable");
+            }
+            self.delivered
+                .lock()
+                .unwrap()
+                .push((recipient.to_string(), subject.to_string()));
+            Ok(())
+        }
+
+        async fn pending_a2a_messages(
+            &self,
+            recipient_agent_id: &str,
+        ) -> anyhow::Result<Vec<String>> {
+            Ok(vec![recipient_agent_id.to_string()])
+        }
+    }
+
+    fn retrying(bus: FailingBus, max_attempts: u32) -> RetryingMessaging<FailingBus> {
+        RetryingMessaging::new(
+            bus,
+            max_attempts,
+            Duration::from_millis(1),
+            Duration::from_millis(2),
+        )
+    }
+
+    #[tokio::test]
+    async fn delivers_after_transient_bus_failures() {
+        let messaging = retrying(FailingBus::failing(2), 3);
+
+        messaging
+            .send_message("board-agent", "escalation", "{}")
+            .await
+            .unwrap();
+
+        assert_eq!(messaging.inner.attempts(), 3);
+        assert_eq!(
+            *messaging.inner.delivered.lock().unwrap(),
+            [("board-agent".to_string(), "escalation".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn gives_up_with_a_delivery_error_after_max_attempts() {
+        let messaging = retrying(FailingBus::failing(10), 4);
+
+        let err = messaging
+            .send_message("board-agent", "escalation", "{}")
+            .await
+            .unwrap_err();
+
+        assert_eq!(messaging.inner.attempts(), 4);
+        assert!(messaging.inner.delivered.lock().unwrap().is_empty());
+        let err = err.downcast::<MessageDeliveryError>().unwrap();
+        assert_eq!(err.recipient, "board-agent");
+        assert_eq!(err.attempts, 4);
+        assert_eq!(err.last_error.to_string(), "bus unavailable");
+    }
+
+    #[test]
+    fn backoff_doubles_from_the_base_delay_up_to_max_delay() {
+        let messaging = RetryingMessaging::new(
+            FailingBus::default(),
+            10,
+            Duration::from_millis(200),
+            Duration::from_millis(2000),
+        );
+
+        let schedule: Vec<u128> = (1..=6)
+            .map(|attempt| messaging.backoff(attempt).as_millis())
+            .collect();
+
+        assert_eq!(schedule, [200, 400, 800, 1600, 2000, 2000]);
+        assert_eq!(messaging.backoff(u32::MAX), Duration::from_millis(2000));
+    }
+
+    #[tokio::test]
+    async fn a2a_sends_are_retried_and_reads_pass_through() {
+        let messaging = retrying(FailingBus::failing(1), 2);
+        let message = A2AMessage::new("procurement-agent", "supplier-agent", "key-1", "payload");
+
+        messaging.send_a2a_message(&message).await.unwrap();
+
+        assert_eq!(messaging.inner.attempts(), 2);
+        assert_eq!(
+            *messaging.inner.delivered.lock().unwrap(),
+            [(
+                "supplier-agent".to_string(),
+                format!("a2a:{}", message.message_id)
+            )]
+        );
+        assert_eq!(
+            messaging
+                .pending_a2a_messages("procurement-agent")
+                .await
+                .unwrap(),
+            ["procurement-agent"]
+        );
+        assert_eq!(messaging.inner.attempts(), 2);
+    }
+
+    fn command_executor(program: &str) -> CommandSkillExecutor {
+        CommandSkillExecutor::new(program, Duration::from_secs(5))
+    }
+
+    #[tokio::test]
+    async fn command_executor_passes_the_request_on_stdin_and_parses_stdout() {
+        let input = serde_json::json!({ "order_id": "order-1" });
+        let context = serde_json::json!({ "dry_run": true });
+
+        let output = command_executor("/bin/cat")
+            .execute("product-fulfillment", "1.0.0", &input, Some(&context))
+            .await
+            .unwrap();
+
+        assert_eq!(output["skill_id"], "product-fulfillment");
+        assert_eq!(output["skill_version"], "1.0.0");
+        assert_eq!(output["input"], input);
+        assert_eq!(output["test_context"], context);
+    }
+
+    #[tokio::test]
+    async fn command_executor_reports_failed_runs_and_non_json_output() {
+        let input = serde_json::json!({});
+
+        let failed = command_executor("/bin/false")
+            .execute("product-fulfillment", "1.0.0", &input, None)
+            .await
+            .unwrap_err();
+        assert!(failed.to_string().contains("exited with"));
+
+        let not_json = command_executor("/bin/echo")
+            .execute("product-fulfillment", "1.0.0", &input, None)
+            .await
+            .unwrap_err();
+        assert!(not_json.to_string().contains("not valid JSON"));
+
+        let missing = command_executor("/nonexistent/skill-runner")
+            .execute("product-fulfillment", "1.0.0", &input, None)
+            .await
+            .unwrap_err();
+        assert!(missing.to_string().contains("failed to start"));
+    }
+
+    #[derive(Debug)]
+    struct GatewayRequest {
+        request_line: String,
+        authorization: String,
+        body: Value,
+    }
+
+    /// Answers each request with `response` and records what was sent, one connection per request.
+    async fn fake_gateway(
+        response: Value,
+    ) -> (String, tokio::sync::mpsc::UnboundedReceiver<GatewayRequest>) {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (requests, received) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let mut reader = BufReader::new(socket);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).await.unwrap();
+                let (mut authorization, mut content_length) = (String::new(), 0);
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).await.unwrap();
+                    let header = header.trim_end();
+                    if header.is_empty() {
+                        break;
+                    }
+                    let (name, value) = header.split_once(": ").unwrap();
+                    match name.to_ascii_lowercase().as_str() {
+                        "authorization" => authorization = value.to_string(),
+                        "content-length" => content_length = value.parse().unwrap(),
+                        _ => {}
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).await.unwrap();
+                let _ = requests.send(GatewayRequest {
+                    request_line: request_line.trim_end().to_string(),
+                    authorization,
+                    body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+                });
+
+                let payload = response.to_string();
+                let reply = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{payload}",
+                    payload.len()
+                );
+                reader
+                    .into_inner()
+                    .write_all(reply.as_bytes())
+                    .await
+                    .unwrap();
+            }
+        });
+        (base_url, received)
+    }
+
+    #[tokio::test]
+    async fn http_order_tool_signs_requests_and_posts_transitions() {
+        let order_id = Uuid::new_v4();
+        let (base_url, mut received) = fake_gateway(serde_json::json!({
+            "order_id": order_id,
+            "item_code": "SKU-001",
+            "quantity": "2.5000",
+            "status": "NEW",
+        }))
+        .await;
+        let orders = HttpOrderTool::new(format!("{base_url}/"), "warehouse-agent", "secret");
+
+        let line = orders.order_line(order_id).await.unwrap();
+        assert_eq!(
+            (line.order_id, line.item_code.as_str(), line.quantity),
+            (order_id, "SKU-001", 2.5)
+        );
+        let get = received.recv().await.unwrap();
+        assert_eq!(get.request_line, format!("GET /orders/{order_id} HTTP/1.1"));
+        let token = get.authorization.strip_prefix("Bearer ").unwrap();
+        let (claims, signature) = token.rsplit_once('.').unwrap();
+        assert!(claims.starts_with("warehouse-agent."));
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(claims.as_bytes());
+        mac.verify_slice(&hex::decode(signature).unwrap()).unwrap();
+
+        orders.fulfill_order(order_id).await.unwrap();
+        let fulfill = received.recv().await.unwrap();
+        assert_eq!(
+            fulfill.request_line,
+            format!("POST /orders/{order_id}/fulfill HTTP/1.1")
+        );
+        assert_eq!(fulfill.body["requested_by_agent_id"], "warehouse-agent");
+
+        orders
+            .mark_awaiting_stock(&OutOfStockEvent {
+                order_id,
+                item_code: "SKU-001".to_string(),
+                requested_quantity: 2.5,
+                available_quantity: 1.0,
+            })
+            .await
+            .unwrap();
+        let parked = received.recv().await.unwrap();
+        assert_eq!(
+            parked.request_line,
+            format!("POST /orders/{order_id}/awaiting-stock HTTP/1.1")
+        );
+        assert_eq!(parked.body["item_code"], "SKU-001");
+        assert_eq!(parked.body["available_quantity"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn mock_payment_tool_records_each_payment_under_its_reference() {
+        let payments = MockPaymentTool::default();
+
+        let first = payments
+            .initiate_payment("supplier@example.com", Decimal::new(12_550, 2), "USD")
+            .await
+            .unwrap();
+        let second = payments
+            .initiate_payment("buyer@example.com", Decimal::from(40), "KES")
+            .await
+            .unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(
+            payments.payments(),
+            [
+                MockPayment {
+                    reference: first,
+                    counterparty: "supplier@example.com".to_string(),
+                    amount: Decimal::new(12_550, 2),
+                    currency: "USD".to_string(),
+                },
+                MockPayment {
+                    reference: second,
+                    counterparty: "buyer@example.com".to_string(),
+                    amount: Decimal::from(40),
+                    currency: "KES".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn mock_payment_tool_failures_record_nothing() {
+        let declined = MockPaymentTool::failing("insufficient funds");
+        let err = declined
+            .initiate_payment("supplier@example.com", Decimal::from(10), "USD")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "payment to supplier@example.com failed: insufficient funds"
+        );
+        assert!(declined.payments().is_empty());
+
+        let payments = MockPaymentTool::default();
+        for amount in [Decimal::ZERO, Decimal::from(-5)] {
+            let err = payments
+                .initiate_payment("supplier@example.com", amount, "USD")
+                .await
+                .unwrap_err();
+            assert_eq!(err.to_string(), "payment amount must be positive");
+        }
+        assert!(payments.payments().is_empty());
+    }
 }
@@ -21,6 +21,47 @@ CREATE INDEX IF NOT EXISTS idx_orders_created_at ON orders(created_at);
 CREATE INDEX IF NOT EXISTS idx_orders_transaction_type ON orders(transaction_type);
 CREATE INDEX IF NOT EXISTS idx_orders_requested_by_agent_id ON orders(requested_by_agent_id);
 
+CREATE TABLE IF NOT EXISTS customers (
+    id UUID PRIMARY KEY,
+    email TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    company TEXT,
+    credit_limit NUMERIC(20, 4) CHECK (credit_limit >= 0),
+    credit_currency TEXT,
+    payment_terms_days INT NOT NULL DEFAULT 30 CHECK (payment_terms_days BETWEEN 0 AND 365),
+    risk_class TEXT NOT NULL DEFAULT 'STANDARD',
+    created_by_agent_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    CHECK (credit_limit IS NULL OR credit_currency IS NOT NULL)
+);
+
+CREATE TABLE IF NOT EXISTS subscriptions (
+    id UUID PRIMARY KEY,
+    customer_email TEXT NOT NULL,
+    item_code TEXT NOT NULL,
+    quantity NUMERIC(20, 4) NOT NULL CHECK (quantity > 0),
+    unit_price NUMERIC(20, 4) NOT NULL CHECK (unit_price > 0),
+    currency TEXT NOT NULL,
+    frequency TEXT NOT NULL CHECK (frequency IN ('WEEKLY', 'MONTHLY', 'QUARTERLY', 'ANNUAL')),
+    next_billing_date DATE NOT NULL,
+    billing_anchor_date DATE NOT NULL,
+    billed_cycles INTEGER NOT NULL DEFAULT 0 CHECK (billed_cycles >= 0),
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    idempotency_date DATE,
+    last_order_id UUID REFERENCES orders(id),
+    created_by_agent_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+ALTER TABLE subscriptions ADD COLUMN IF NOT EXISTS billing_anchor_date DATE;
+ALTER TABLE subscriptions ADD COLUMN IF NOT EXISTS billed_cycles INTEGER NOT NULL DEFAULT 0;
+UPDATE subscriptions SET billing_anchor_date = next_billing_date WHERE billing_anchor_date IS NULL;
+ALTER TABLE subscriptions ALTER COLUMN billing_anchor_date SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_subscriptions_due ON subscriptions(active, next_billing_date);
+
 ALTER TABLE orders ADD COLUMN IF NOT EXISTS transaction_type TEXT;
 ALTER TABLE orders ADD COLUMN IF NOT EXISTS requested_by_agent_id TEXT;
 
@@ -51,6 +92,35 @@ BEGIN
 END
 $$;
 
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS fulfillment_sla_hours INTEGER NOT NULL DEFAULT 72 CHECK (fulfillment_sla_hours > 0);
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS sla_deadline_at TIMESTAMPTZ;
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS sla_status TEXT NOT NULL DEFAULT 'ON_TIME' CHECK (sla_status IN ('ON_TIME', 'BREACHED'));
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS sla_escalation_id UUID;
+
+UPDATE orders
+SET sla_deadline_at = created_at + fulfillment_sla_hours * INTERVAL '1 hour'
+WHERE sla_deadline_at IS NULL;
+
+ALTER TABLE orders ALTER COLUMN sla_deadline_at SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_orders_sla_deadline ON orders(status, sla_status, sla_deadline_at);
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1
+        FROM pg_constraint
+        WHERE conname = 'orders_status_check'
+    ) THEN
+        ALTER TABLE orders
+            ADD CONSTRAINT orders_status_check
+            CHECK (status IN (
+                'NEW', 'PENDING_APPROVAL', 'IN_PROGRESS', 'AWAITING_STOCK', 'FULFILLED', 'FAILED', 'FROZEN'
+            ));
+    END IF;
+END
+$$;
+
 CREATE TABLE IF NOT EXISTS leads (
     id UUID PRIMARY KEY,
     contact_email TEXT NOT NULL,
@@ -63,6 +133,48 @@ CREATE TABLE IF NOT EXISTS leads (
 
 CREATE INDEX IF NOT EXISTS idx_leads_created_at ON leads(created_at);
 CREATE INDEX IF NOT EXISTS idx_leads_status ON leads(status);
+ALTER TABLE leads ADD COLUMN IF NOT EXISTS merged_into_lead_id UUID REFERENCES leads(id);
+ALTER TABLE leads ADD COLUMN IF NOT EXISTS merged_at TIMESTAMPTZ;
+ALTER TABLE leads DROP CONSTRAINT IF EXISTS leads_status_check;
+ALTER TABLE leads
+    ADD CONSTRAINT leads_status_check
+    CHECK (
+        status IN (
+            'NEW', 'CONTACTED', 'QUALIFIED', 'DISQUALIFIED', 'CONVERTED', 'DROPPED', 'MERGED'
+        )
+    );
+
+CREATE INDEX IF NOT EXISTS idx_leads_contact_email_lower ON leads(LOWER(contact_email), created_at);
+CREATE INDEX IF NOT EXISTS idx_leads_source_channel ON leads(source_channel, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS lead_status_history (
+    id UUID PRIMARY KEY,
+    lead_id UUID NOT NULL REFERENCES leads(id),
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    note TEXT,
+    changed_by_agent_id TEXT NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_lead_status_history_lead
+    ON lead_status_history(lead_id, changed_at);
+
+CREATE TABLE IF NOT EXISTS channel_score_weights (
+    source_channel TEXT PRIMARY KEY,
+    weight INTEGER NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS lead_scores (
+    lead_id UUID PRIMARY KEY REFERENCES leads(id),
+    score INTEGER NOT NULL CHECK (score BETWEEN 0 AND 100),
+    score_reason_json JSONB NOT NULL,
+    scored_at TIMESTAMPTZ NOT NULL,
+    scored_by_agent_id TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_lead_scores_score ON lead_scores(score DESC);
 
 CREATE TABLE IF NOT EXISTS opportunities (
     id UUID PRIMARY KEY,
@@ -80,9 +192,23 @@ CREATE TABLE IF NOT EXISTS opportunities (
     updated_at TIMESTAMPTZ NOT NULL
 );
 
+ALTER TABLE opportunities ADD COLUMN IF NOT EXISTS lost_reason_code TEXT;
+ALTER TABLE opportunities ADD COLUMN IF NOT EXISTS lost_note TEXT;
+ALTER TABLE opportunities ADD COLUMN IF NOT EXISTS lost_at TIMESTAMPTZ;
+ALTER TABLE opportunities ADD COLUMN IF NOT EXISTS close_reason_code TEXT;
+ALTER TABLE opportunities ADD COLUMN IF NOT EXISTS close_reason_note TEXT;
+ALTER TABLE opportunities ADD COLUMN IF NOT EXISTS closed_at TIMESTAMPTZ;
+ALTER TABLE opportunities ADD COLUMN IF NOT EXISTS closed_by_agent_id TEXT;
+ALTER TABLE opportunities ADD COLUMN IF NOT EXISTS won_order_id UUID REFERENCES orders(id);
+ALTER TABLE opportunities DROP CONSTRAINT IF EXISTS opportunities_stage_check;
+ALTER TABLE opportunities
+    ADD CONSTRAINT opportunities_stage_check
+    CHECK (stage IN ('QUALIFIED', 'PROPOSAL', 'ACCEPTED', 'WON', 'LOST'));
+
 CREATE INDEX IF NOT EXISTS idx_opportunities_lead_id ON opportunities(lead_id);
 CREATE INDEX IF NOT EXISTS idx_opportunities_stage ON opportunities(stage);
 CREATE INDEX IF NOT EXISTS idx_opportunities_created_at ON opportunities(created_at);
+CREATE INDEX IF NOT EXISTS idx_opportunities_closed_at ON opportunities(closed_at);
 
 CREATE TABLE IF NOT EXISTS quotes (
     id UUID PRIMARY KEY,
@@ -94,15 +220,25 @@ CREATE TABLE IF NOT EXISTS quotes (
     valid_until TIMESTAMPTZ NOT NULL,
     terms_json JSONB NOT NULL DEFAULT '{}'::jsonb,
     risk_metadata JSONB NOT NULL DEFAULT '{}'::jsonb,
-    status TEXT NOT NULL CHECK (status IN ('ISSUED', 'ACCEPTED', 'EXPIRED', 'REJECTED')),
+    status TEXT NOT NULL CHECK (status IN ('ISSUED', 'ACCEPTED', 'EXPIRED', 'REJECTED', 'SUPERSEDED')),
     requested_by_agent_id TEXT NOT NULL,
     created_at TIMESTAMPTZ NOT NULL,
-    updated_at TIMESTAMPTZ NOT NULL
+    updated_at TIMESTAMPTZ NOT NULL,
+    supersedes_quote_id UUID REFERENCES quotes(id),
+    revision_number INTEGER NOT NULL DEFAULT 1
 );
 
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS supersedes_quote_id UUID REFERENCES quotes(id);
+ALTER TABLE quotes ADD COLUMN IF NOT EXISTS revision_number INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE quotes DROP CONSTRAINT IF EXISTS quotes_status_check;
+ALTER TABLE quotes
+    ADD CONSTRAINT quotes_status_check
+    CHECK (status IN ('ISSUED', 'ACCEPTED', 'EXPIRED', 'REJECTED', 'SUPERSEDED'));
+
 CREATE INDEX IF NOT EXISTS idx_quotes_opportunity_id ON quotes(opportunity_id);
 CREATE INDEX IF NOT EXISTS idx_quotes_status ON quotes(status);
 CREATE INDEX IF NOT EXISTS idx_quotes_valid_until ON quotes(valid_until);
+CREATE INDEX IF NOT EXISTS idx_quotes_supersedes_quote_id ON quotes(supersedes_quote_id);
 
 CREATE TABLE IF NOT EXISTS quote_acceptances (
     id UUID PRIMARY KEY,
@@ -139,6 +275,20 @@ CREATE TABLE IF NOT EXISTS origination_channel_proofs (
     UNIQUE (channel_type, message_id)
 );
 
+CREATE TABLE IF NOT EXISTS webhook_secrets (
+    source_system TEXT PRIMARY KEY,
+    secret TEXT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+ALTER TABLE origination_channel_proofs DROP CONSTRAINT IF EXISTS origination_channel_proofs_channel_type_check;
+ALTER TABLE origination_channel_proofs
+    ADD CONSTRAINT origination_channel_proofs_channel_type_check
+    CHECK (channel_type IN ('EMAIL', 'WEBHOOK', 'SMS', 'CHAT', 'DOCUMENT'));
+ALTER TABLE origination_channel_proofs ADD COLUMN IF NOT EXISTS attachment_url TEXT;
+ALTER TABLE origination_channel_proofs ADD COLUMN IF NOT EXISTS attachment_hash TEXT;
+
 CREATE INDEX IF NOT EXISTS idx_origination_channel_proofs_lead_id
     ON origination_channel_proofs(lead_id, captured_at DESC);
 CREATE INDEX IF NOT EXISTS idx_origination_channel_proofs_opportunity_id
@@ -154,11 +304,35 @@ CREATE TABLE IF NOT EXISTS governance_thresholds (
     action_type TEXT PRIMARY KEY,
     max_auto_amount NUMERIC(20, 4) NOT NULL CHECK (max_auto_amount > 0),
     currency TEXT NOT NULL DEFAULT 'USD',
+    always_escalate BOOLEAN NOT NULL DEFAULT FALSE,
     active BOOLEAN NOT NULL DEFAULT TRUE,
     updated_by_agent_id TEXT NOT NULL,
     updated_at TIMESTAMPTZ NOT NULL
 );
 
+ALTER TABLE governance_thresholds ADD COLUMN IF NOT EXISTS always_escalate BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE governance_thresholds
+    ADD COLUMN IF NOT EXISTS sla_hours BIGINT NOT NULL DEFAULT 24 CHECK (sla_hours > 0);
+
+CREATE TABLE IF NOT EXISTS governance_threshold_history (
+    id UUID PRIMARY KEY,
+    action_type TEXT NOT NULL,
+    max_auto_amount NUMERIC(20, 4) NOT NULL,
+    currency TEXT NOT NULL,
+    always_escalate BOOLEAN NOT NULL DEFAULT FALSE,
+    sla_hours BIGINT NOT NULL DEFAULT 24,
+    active BOOLEAN NOT NULL,
+    updated_by_agent_id TEXT NOT NULL,
+    effective_from TIMESTAMPTZ NOT NULL,
+    superseded_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_governance_threshold_history_action_effective
+    ON governance_threshold_history(action_type, effective_from DESC);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_governance_threshold_history_current
+    ON governance_threshold_history(action_type)
+    WHERE superseded_at IS NULL;
+
 CREATE TABLE IF NOT EXISTS governance_freeze_controls (
     action_type TEXT PRIMARY KEY,
     is_frozen BOOLEAN NOT NULL DEFAULT FALSE,
@@ -167,6 +341,22 @@ CREATE TABLE IF NOT EXISTS governance_freeze_controls (
     updated_at TIMESTAMPTZ NOT NULL
 );
 
+CREATE TABLE IF NOT EXISTS governance_freeze_history (
+    id UUID PRIMARY KEY,
+    action_type TEXT NOT NULL,
+    is_frozen BOOLEAN NOT NULL,
+    reason TEXT,
+    updated_by_agent_id TEXT NOT NULL,
+    effective_from TIMESTAMPTZ NOT NULL,
+    superseded_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_governance_freeze_history_action_effective
+    ON governance_freeze_history(action_type, effective_from DESC);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_governance_freeze_history_current
+    ON governance_freeze_history(action_type)
+    WHERE superseded_at IS NULL;
+
 CREATE TABLE IF NOT EXISTS governance_escalations (
     id UUID PRIMARY KEY,
     action_type TEXT NOT NULL,
@@ -188,6 +378,19 @@ CREATE INDEX IF NOT EXISTS idx_governance_escalations_status_created
 CREATE INDEX IF NOT EXISTS idx_governance_escalations_reference
     ON governance_escalations(reference_type, reference_id);
 
+ALTER TABLE governance_escalations ADD COLUMN IF NOT EXISTS sla_deadline_at TIMESTAMPTZ;
+ALTER TABLE governance_escalations
+    ADD COLUMN IF NOT EXISTS sla_status TEXT NOT NULL DEFAULT 'ON_TIME'
+    CHECK (sla_status IN ('ON_TIME', 'OVERDUE'));
+
+UPDATE governance_escalations
+SET sla_deadline_at = created_at + INTERVAL '24 hours'
+WHERE sla_deadline_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_governance_escalations_sla
+    ON governance_escalations(sla_deadline_at)
+    WHERE status = 'PENDING' AND sla_status = 'ON_TIME';
+
 CREATE TABLE IF NOT EXISTS strategy_offerings (
     id UUID PRIMARY KEY,
     offering_code TEXT NOT NULL UNIQUE,
@@ -206,6 +409,39 @@ CREATE TABLE IF NOT EXISTS strategy_offerings (
 CREATE INDEX IF NOT EXISTS idx_strategy_offerings_type_active
     ON strategy_offerings(offering_type, active, updated_at DESC);
 
+CREATE TABLE IF NOT EXISTS offering_price_tiers (
+    id UUID PRIMARY KEY,
+    offering_code TEXT NOT NULL REFERENCES strategy_offerings(offering_code) ON DELETE CASCADE,
+    min_quantity NUMERIC(20, 4) NOT NULL CHECK (min_quantity >= 0),
+    max_quantity NUMERIC(20, 4),
+    unit_price NUMERIC(20, 4) NOT NULL CHECK (unit_price > 0),
+    currency TEXT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by_agent_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    CHECK (max_quantity IS NULL OR max_quantity >= min_quantity)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_offering_price_tiers_active_min
+    ON offering_price_tiers(offering_code, currency, min_quantity)
+    WHERE active;
+
+CREATE TABLE IF NOT EXISTS quote_lines (
+    id UUID PRIMARY KEY,
+    quote_id UUID NOT NULL REFERENCES quotes(id) ON DELETE CASCADE,
+    line_no INTEGER NOT NULL CHECK (line_no > 0),
+    transaction_type TEXT NOT NULL CHECK (transaction_type IN ('PRODUCT', 'SERVICE')),
+    item_code TEXT NOT NULL,
+    quantity NUMERIC(20, 4) NOT NULL CHECK (quantity > 0),
+    unit_price NUMERIC(20, 4) NOT NULL CHECK (unit_price > 0),
+    currency TEXT NOT NULL,
+    discount_pct NUMERIC(7, 4) NOT NULL DEFAULT 0 CHECK (discount_pct >= 0 AND discount_pct < 100),
+    line_amount NUMERIC(20, 4) NOT NULL CHECK (line_amount > 0),
+    applied_tier_id UUID REFERENCES offering_price_tiers(id),
+    order_id UUID UNIQUE REFERENCES orders(id),
+    UNIQUE (quote_id, line_no)
+);
+
 CREATE TABLE IF NOT EXISTS strategy_kpi_targets (
     id UUID PRIMARY KEY,
     period_start DATE NOT NULL,
@@ -220,14 +456,40 @@ CREATE TABLE IF NOT EXISTS strategy_kpi_targets (
     updated_by_agent_id TEXT NOT NULL,
     created_at TIMESTAMPTZ NOT NULL,
     updated_at TIMESTAMPTZ NOT NULL,
+    valid_from TIMESTAMPTZ NOT NULL,
     CHECK (period_end >= period_start),
-    UNIQUE (period_start, period_end, business_unit, mandate, metric_name)
+    UNIQUE (period_start, period_end, business_unit, mandate, metric_name, valid_from)
 );
 
+ALTER TABLE strategy_kpi_targets ADD COLUMN IF NOT EXISTS valid_from TIMESTAMPTZ;
+UPDATE strategy_kpi_targets SET valid_from = created_at WHERE valid_from IS NULL;
+ALTER TABLE strategy_kpi_targets ALTER COLUMN valid_from SET NOT NULL;
+
+DO $$
+DECLARE
+    legacy_constraint TEXT;
+BEGIN
+    SELECT c.conname INTO legacy_constraint
+    FROM pg_constraint c
+    WHERE c.conrelid = 'strategy_kpi_targets'::regclass
+      AND c.contype = 'u'
+      AND array_length(c.conkey, 1) = 5;
+
+    IF legacy_constraint IS NOT NULL THEN
+        EXECUTE format('ALTER TABLE strategy_kpi_targets DROP CONSTRAINT %I', legacy_constraint);
+        ALTER TABLE strategy_kpi_targets
+            ADD CONSTRAINT strategy_kpi_targets_key_valid_from_key
+            UNIQUE (period_start, period_end, business_unit, mandate, metric_name, valid_from);
+    END IF;
+END
+$$;
+
 CREATE INDEX IF NOT EXISTS idx_strategy_kpi_targets_period
     ON strategy_kpi_targets(period_start DESC, period_end DESC);
 CREATE INDEX IF NOT EXISTS idx_strategy_kpi_targets_dimension
     ON strategy_kpi_targets(business_unit, mandate, metric_name);
+CREATE INDEX IF NOT EXISTS idx_strategy_kpi_targets_effective
+    ON strategy_kpi_targets(business_unit, mandate, metric_name, period_start, period_end, valid_from DESC);
 
 CREATE TABLE IF NOT EXISTS strategy_forecasts (
     id UUID PRIMARY KEY,
@@ -252,6 +514,21 @@ CREATE INDEX IF NOT EXISTS idx_strategy_forecasts_period
 CREATE INDEX IF NOT EXISTS idx_strategy_forecasts_dimension
     ON strategy_forecasts(business_unit, mandate, metric_name);
 
+CREATE TABLE IF NOT EXISTS strategy_forecast_adjustments (
+    id UUID PRIMARY KEY,
+    forecast_id UUID NOT NULL REFERENCES strategy_forecasts(id) ON DELETE CASCADE,
+    old_value NUMERIC(20, 4) NOT NULL,
+    new_value NUMERIC(20, 4) NOT NULL CHECK (new_value >= 0),
+    adjustment_type TEXT NOT NULL CHECK (adjustment_type IN ('ABSOLUTE', 'RELATIVE_PCT')),
+    adjustment_value NUMERIC(20, 4) NOT NULL,
+    note TEXT,
+    adjusted_by_agent_id TEXT NOT NULL,
+    adjusted_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_strategy_forecast_adjustments_forecast
+    ON strategy_forecast_adjustments(forecast_id, adjusted_at DESC);
+
 CREATE TABLE IF NOT EXISTS strategy_variances (
     id UUID PRIMARY KEY,
     period_start DATE NOT NULL,
@@ -290,10 +567,31 @@ CREATE TABLE IF NOT EXISTS strategy_corrective_actions (
     closed_at TIMESTAMPTZ
 );
 
+ALTER TABLE strategy_corrective_actions ADD COLUMN IF NOT EXISTS closed_by_agent_id TEXT;
+ALTER TABLE strategy_corrective_actions ADD COLUMN IF NOT EXISTS resolution_note TEXT;
+ALTER TABLE strategy_corrective_actions ADD COLUMN IF NOT EXISTS resolution_outcome TEXT;
+ALTER TABLE strategy_corrective_actions
+    DROP CONSTRAINT IF EXISTS strategy_corrective_actions_resolution_outcome_check;
+ALTER TABLE strategy_corrective_actions
+    ADD CONSTRAINT strategy_corrective_actions_resolution_outcome_check
+    CHECK (resolution_outcome IS NULL OR resolution_outcome IN ('RESOLVED', 'PARTIALLY_RESOLVED', 'FAILED'));
+ALTER TABLE strategy_corrective_actions ADD COLUMN IF NOT EXISTS responsible_agent_id TEXT;
+ALTER TABLE strategy_corrective_actions ADD COLUMN IF NOT EXISTS target_completion_date DATE;
+ALTER TABLE strategy_corrective_actions ADD COLUMN IF NOT EXISTS assignment_note TEXT;
+ALTER TABLE strategy_corrective_actions ADD COLUMN IF NOT EXISTS assigned_by_agent_id TEXT;
+ALTER TABLE strategy_corrective_actions ADD COLUMN IF NOT EXISTS assigned_at TIMESTAMPTZ;
+ALTER TABLE strategy_corrective_actions
+    ADD COLUMN IF NOT EXISTS overdue_escalation_id UUID REFERENCES governance_escalations(id);
+
 CREATE INDEX IF NOT EXISTS idx_strategy_corrective_actions_status_created
     ON strategy_corrective_actions(status, created_at DESC);
 CREATE INDEX IF NOT EXISTS idx_strategy_corrective_actions_variance
     ON strategy_corrective_actions(variance_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_strategy_corrective_actions_responsible
+    ON strategy_corrective_actions(responsible_agent_id, status);
+CREATE INDEX IF NOT EXISTS idx_strategy_corrective_actions_target_open
+    ON strategy_corrective_actions(target_completion_date)
+    WHERE status = 'OPEN';
 
 CREATE TABLE IF NOT EXISTS inventory_positions (
     item_code TEXT PRIMARY KEY,
@@ -381,6 +679,140 @@ CREATE INDEX IF NOT EXISTS idx_ar_subledger_invoice_posted_at
 CREATE INDEX IF NOT EXISTS idx_ar_subledger_order_posted_at
     ON ar_subledger_entries(order_id, posted_at);
 
+CREATE TABLE IF NOT EXISTS credit_memos (
+    id UUID PRIMARY KEY,
+    credit_memo_number TEXT NOT NULL UNIQUE,
+    order_id UUID NOT NULL REFERENCES orders(id),
+    invoice_id UUID NOT NULL REFERENCES invoices(id),
+    item_code TEXT NOT NULL,
+    quantity NUMERIC(20, 4) NOT NULL CHECK (quantity > 0),
+    unit_price NUMERIC(20, 4) NOT NULL CHECK (unit_price >= 0),
+    unit_cost NUMERIC(20, 4) NOT NULL CHECK (unit_cost >= 0),
+    amount NUMERIC(20, 4) NOT NULL CHECK (amount >= 0),
+    cost_amount NUMERIC(20, 4) NOT NULL CHECK (cost_amount >= 0),
+    customer_credit_amount NUMERIC(20, 4) NOT NULL DEFAULT 0 CHECK (customer_credit_amount >= 0),
+    currency TEXT NOT NULL,
+    reason TEXT,
+    created_by_agent_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_credit_memos_order_id
+    ON credit_memos(order_id, created_at);
+
+CREATE TABLE IF NOT EXISTS event_outbox (
+    id UUID PRIMARY KEY,
+    topic TEXT NOT NULL,
+    aggregate_id UUID NOT NULL,
+    payload_json JSONB NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    next_attempt_at TIMESTAMPTZ NOT NULL,
+    published_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_outbox_unpublished
+    ON event_outbox(next_attempt_at)
+    WHERE published_at IS NULL;
+
+CREATE TABLE IF NOT EXISTS domain_events (
+    sequence BIGSERIAL PRIMARY KEY,
+    id UUID NOT NULL UNIQUE,
+    stream_id UUID NOT NULL,
+    aggregate_id UUID NOT NULL,
+    kind TEXT NOT NULL,
+    kind_json JSONB NOT NULL,
+    payload_json JSONB NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    stored_at TIMESTAMPTZ NOT NULL
+);
+
+ALTER TABLE domain_events ADD COLUMN IF NOT EXISTS schema_version INT NOT NULL DEFAULT 1;
+
+CREATE INDEX IF NOT EXISTS idx_domain_events_stream ON domain_events(stream_id, sequence);
+CREATE INDEX IF NOT EXISTS idx_domain_events_kind ON domain_events(kind, occurred_at);
+
+CREATE TABLE IF NOT EXISTS a2a_messages (
+    message_id UUID PRIMARY KEY,
+    correlation_id UUID NOT NULL,
+    causation_id UUID,
+    idempotency_key TEXT NOT NULL,
+    schema_version TEXT NOT NULL,
+    sender_agent_id TEXT NOT NULL,
+    recipient_agent_id TEXT NOT NULL,
+    message_json JSONB NOT NULL,
+    sent_at TIMESTAMPTZ NOT NULL,
+    replay_count INT NOT NULL DEFAULT 0,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    published_at TIMESTAMPTZ,
+    delivered_at TIMESTAMPTZ,
+    UNIQUE (sender_agent_id, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_a2a_messages_inbox
+    ON a2a_messages(recipient_agent_id, sent_at)
+    WHERE delivered_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_a2a_messages_correlation
+    ON a2a_messages(correlation_id, sent_at);
+
+CREATE TABLE IF NOT EXISTS dead_letter_events (
+    id UUID PRIMARY KEY,
+    topic TEXT NOT NULL,
+    aggregate_id UUID NOT NULL,
+    payload_json JSONB NOT NULL,
+    attempts INT NOT NULL,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    dead_lettered_at TIMESTAMPTZ NOT NULL,
+    discarded_at TIMESTAMPTZ,
+    discarded_by_agent_id TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_dead_letter_events_pending
+    ON dead_letter_events(dead_lettered_at)
+    WHERE discarded_at IS NULL;
+
+CREATE TABLE IF NOT EXISTS audit_requests (
+    id UUID PRIMARY KEY,
+    order_id UUID NOT NULL REFERENCES orders(id),
+    status TEXT NOT NULL CHECK (status IN ('PENDING', 'COMPLETED')),
+    requested_by_agent_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_requests_status
+    ON audit_requests(status, created_at);
+
+CREATE TABLE IF NOT EXISTS audit_evidence_store (
+    id UUID PRIMARY KEY,
+    audit_request_id UUID NOT NULL REFERENCES audit_requests(id),
+    order_id UUID NOT NULL REFERENCES orders(id),
+    evidence_hash TEXT NOT NULL,
+    evidence_json JSONB NOT NULL,
+    generated_by_agent_id TEXT NOT NULL,
+    generated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_evidence_store_order
+    ON audit_evidence_store(order_id, generated_at DESC);
+
+CREATE TABLE IF NOT EXISTS audit_evidence_bundles (
+    id UUID PRIMARY KEY,
+    order_id UUID NOT NULL REFERENCES orders(id),
+    digest TEXT NOT NULL,
+    hash_algorithm TEXT NOT NULL,
+    signature TEXT,
+    first_generated_at TIMESTAMPTZ NOT NULL,
+    last_generated_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (order_id, digest)
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_evidence_bundles_order
+    ON audit_evidence_bundles(order_id, last_generated_at DESC);
+
 CREATE TABLE IF NOT EXISTS ap_obligations (
     id UUID PRIMARY KEY,
     order_id UUID NOT NULL REFERENCES orders(id),
@@ -420,6 +852,55 @@ CREATE INDEX IF NOT EXISTS idx_ap_subledger_obligation_posted_at
 CREATE INDEX IF NOT EXISTS idx_ap_subledger_order_posted_at
     ON ap_subledger_entries(order_id, posted_at);
 
+CREATE TABLE IF NOT EXISTS procurement_orders (
+    id UUID PRIMARY KEY,
+    order_id UUID NOT NULL REFERENCES orders(id),
+    supplier TEXT NOT NULL,
+    item_code TEXT NOT NULL,
+    quantity_ordered NUMERIC(20, 4) NOT NULL CHECK (quantity_ordered > 0),
+    unit_price NUMERIC(20, 4) NOT NULL CHECK (unit_price > 0),
+    currency TEXT NOT NULL,
+    created_by_agent_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_procurement_orders_order_id
+    ON procurement_orders(order_id, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS goods_receipts (
+    id UUID PRIMARY KEY,
+    po_id UUID NOT NULL REFERENCES procurement_orders(id),
+    quantity_received NUMERIC(20, 4) NOT NULL CHECK (quantity_received > 0),
+    received_by_agent_id TEXT NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_goods_receipts_po_id
+    ON goods_receipts(po_id, received_at);
+
+CREATE TABLE IF NOT EXISTS three_way_matches (
+    id UUID PRIMARY KEY,
+    po_id UUID NOT NULL REFERENCES procurement_orders(id),
+    goods_receipt_id UUID NOT NULL REFERENCES goods_receipts(id),
+    supplier_invoice_ref TEXT NOT NULL,
+    supplier_invoice_amount NUMERIC(20, 4) NOT NULL CHECK (supplier_invoice_amount > 0),
+    expected_amount NUMERIC(20, 4) NOT NULL,
+    variance_amount NUMERIC(20, 4) NOT NULL,
+    variance_pct NUMERIC(12, 4) NOT NULL,
+    tolerance_pct NUMERIC(12, 4) NOT NULL,
+    match_passed BOOLEAN NOT NULL,
+    escalation_id UUID REFERENCES governance_escalations(id),
+    ap_obligation_id UUID REFERENCES ap_obligations(id),
+    requested_by_agent_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_three_way_matches_passed_receipt
+    ON three_way_matches(goods_receipt_id)
+    WHERE match_passed;
+CREATE INDEX IF NOT EXISTS idx_three_way_matches_po_id
+    ON three_way_matches(po_id, created_at DESC);
+
 CREATE TABLE IF NOT EXISTS agent_semantic_memory (
     id UUID PRIMARY KEY,
     agent_name TEXT NOT NULL,
@@ -442,6 +923,14 @@ CREATE INDEX IF NOT EXISTS idx_agent_semantic_memory_keywords
 CREATE INDEX IF NOT EXISTS idx_agent_semantic_memory_fts
     ON agent_semantic_memory USING GIN (to_tsvector('simple', content));
 
+ALTER TABLE agent_semantic_memory
+    ADD COLUMN IF NOT EXISTS ttl_days INTEGER CHECK (ttl_days > 0);
+ALTER TABLE agent_semantic_memory ADD COLUMN IF NOT EXISTS tombstoned_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_agent_semantic_memory_ttl
+    ON agent_semantic_memory(created_at)
+    WHERE ttl_days IS NOT NULL;
+
 CREATE TABLE IF NOT EXISTS agent_memory_provenance (
     id UUID PRIMARY KEY,
     memory_id UUID REFERENCES agent_semantic_memory(id) ON DELETE CASCADE,
@@ -491,6 +980,39 @@ VALUES
     ('ORDER_EXECUTION_SERVICE', 5000.0000, 'USD', TRUE, 'board-agent', NOW())
 ON CONFLICT (action_type) DO NOTHING;
 
+INSERT INTO governance_threshold_history(
+    id, action_type, max_auto_amount, currency, always_escalate, sla_hours, active,
+    updated_by_agent_id, effective_from, superseded_at
+)
+SELECT
+    uuid_generate_v4(),
+    t.action_type,
+    t.max_auto_amount,
+    t.currency,
+    t.always_escalate,
+    t.sla_hours,
+    t.active,
+    t.updated_by_agent_id,
+    t.updated_at,
+    NULL
+FROM governance_thresholds t
+WHERE NOT EXISTS (
+    SELECT 1 FROM governance_threshold_history h WHERE h.action_type = t.action_type
+);
+
+INSERT INTO channel_score_weights(source_channel, weight, updated_at)
+VALUES
+    ('DOCUMENT', 30, NOW()),
+    ('EMAIL', 20, NOW()),
+    ('WEBHOOK', 15, NOW()),
+    ('CHAT', 15, NOW()),
+    ('SMS', 10, NOW())
+ON CONFLICT (source_channel) DO NOTHING;
+
+INSERT INTO webhook_secrets(source_system, secret, active, updated_at)
+VALUES ('crm', 'demo-crm-webhook-secret', TRUE, NOW())
+ON CONFLICT (source_system) DO NOTHING;
+
 INSERT INTO governance_freeze_controls(
     action_type, is_frozen, reason, updated_by_agent_id, updated_at
 )
@@ -499,6 +1021,22 @@ VALUES
     ('ORDER_EXECUTION_SERVICE', FALSE, NULL, 'board-agent', NOW())
 ON CONFLICT (action_type) DO NOTHING;
 
+INSERT INTO governance_freeze_history(
+    id, action_type, is_frozen, reason, updated_by_agent_id, effective_from, superseded_at
+)
+SELECT
+    uuid_generate_v4(),
+    f.action_type,
+    f.is_frozen,
+    f.reason,
+    f.updated_by_agent_id,
+    f.updated_at,
+    NULL
+FROM governance_freeze_controls f
+WHERE NOT EXISTS (
+    SELECT 1 FROM governance_freeze_history h WHERE h.action_type = f.action_type
+);
+
 INSERT INTO strategy_offerings(
     id,
     offering_code,
@@ -554,7 +1092,8 @@ INSERT INTO strategy_kpi_targets(
     currency,
     updated_by_agent_id,
     created_at,
-    updated_at
+    updated_at,
+    valid_from
 )
 VALUES
     (
@@ -570,7 +1109,8 @@ VALUES
         'USD',
         'strategy-agent',
         NOW(),
-        NOW()
+        NOW(),
+        date_trunc('month', NOW())
     ),
     (
         uuid_generate_v4(),
@@ -585,9 +1125,10 @@ VALUES
         'USD',
         'strategy-agent',
         NOW(),
-        NOW()
+        NOW(),
+        date_trunc('month', NOW())
     )
-ON CONFLICT (period_start, period_end, business_unit, mandate, metric_name) DO NOTHING;
+ON CONFLICT (period_start, period_end, business_unit, mandate, metric_name, valid_from) DO NOTHING;
 
 INSERT INTO strategy_forecasts(
     id,
@@ -661,6 +1202,9 @@ CREATE INDEX IF NOT EXISTS idx_finops_token_usage_order_id
     ON finops_token_usage(order_id);
 CREATE INDEX IF NOT EXISTS idx_finops_token_usage_agent_id
     ON finops_token_usage(agent_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_finops_token_usage_source_ref
+    ON finops_token_usage(agent_id, source_ref)
+    WHERE source_ref IS NOT NULL;
 
 CREATE TABLE IF NOT EXISTS finops_cloud_costs (
     id UUID PRIMARY KEY,
@@ -681,6 +1225,9 @@ CREATE INDEX IF NOT EXISTS idx_finops_cloud_costs_occurred_at
     ON finops_cloud_costs(occurred_at DESC);
 CREATE INDEX IF NOT EXISTS idx_finops_cloud_costs_order_id
     ON finops_cloud_costs(order_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_finops_cloud_costs_source_ref
+    ON finops_cloud_costs(provider, source_ref)
+    WHERE source_ref IS NOT NULL;
 
 CREATE TABLE IF NOT EXISTS finops_subscription_costs (
     id UUID PRIMARY KEY,
@@ -698,6 +1245,40 @@ CREATE TABLE IF NOT EXISTS finops_subscription_costs (
 
 CREATE INDEX IF NOT EXISTS idx_finops_subscription_costs_period
     ON finops_subscription_costs(period_start, period_end);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_finops_subscription_costs_natural_key
+    ON finops_subscription_costs(tool_name, subscription_name, period_start, period_end);
+
+ALTER TABLE finops_subscription_costs
+    ADD COLUMN IF NOT EXISTS superseded_by_id UUID REFERENCES finops_subscription_costs(id);
+ALTER TABLE finops_subscription_costs ADD COLUMN IF NOT EXISTS superseded_at TIMESTAMPTZ;
+
+UPDATE finops_token_usage SET currency = UPPER(BTRIM(currency))
+WHERE currency <> UPPER(BTRIM(currency));
+UPDATE finops_cloud_costs SET currency = UPPER(BTRIM(currency))
+WHERE currency <> UPPER(BTRIM(currency));
+UPDATE finops_subscription_costs SET currency = UPPER(BTRIM(currency))
+WHERE currency <> UPPER(BTRIM(currency));
+
+CREATE INDEX IF NOT EXISTS idx_finops_subscription_costs_active
+    ON finops_subscription_costs(tool_name, subscription_name, period_start, period_end)
+    WHERE superseded_by_id IS NULL;
+
+CREATE TABLE IF NOT EXISTS finops_subscription_cost_revisions (
+    id UUID PRIMARY KEY,
+    subscription_cost_id UUID NOT NULL REFERENCES finops_subscription_costs(id),
+    previous_period_start TIMESTAMPTZ NOT NULL,
+    previous_period_end TIMESTAMPTZ NOT NULL,
+    previous_total_cost NUMERIC(20, 4) NOT NULL CHECK (previous_total_cost >= 0),
+    period_start TIMESTAMPTZ NOT NULL,
+    period_end TIMESTAMPTZ NOT NULL,
+    total_cost NUMERIC(20, 4) NOT NULL CHECK (total_cost >= 0),
+    reason TEXT,
+    revised_by_agent_id TEXT NOT NULL,
+    revised_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_finops_subscription_cost_revisions_cost
+    ON finops_subscription_cost_revisions(subscription_cost_id, revised_at);
 
 CREATE TABLE IF NOT EXISTS finops_cost_allocations (
     id UUID PRIMARY KEY,
@@ -711,12 +1292,20 @@ CREATE TABLE IF NOT EXISTS finops_cost_allocations (
     allocation_basis TEXT NOT NULL CHECK (allocation_basis IN ('DIRECT_ORDER', 'REVENUE_SHARE')),
     allocated_cost NUMERIC(20, 4) NOT NULL CHECK (allocated_cost >= 0),
     currency TEXT NOT NULL,
-    created_at TIMESTAMPTZ NOT NULL
+    created_at TIMESTAMPTZ NOT NULL,
+    namespace TEXT NOT NULL DEFAULT ''
 );
 
+ALTER TABLE finops_cost_allocations ADD COLUMN IF NOT EXISTS namespace TEXT NOT NULL DEFAULT '';
+ALTER TABLE finops_cost_allocations DROP CONSTRAINT IF EXISTS finops_cost_allocations_allocation_basis_check;
+ALTER TABLE finops_cost_allocations
+    ADD CONSTRAINT finops_cost_allocations_allocation_basis_check
+    CHECK (allocation_basis IN ('DIRECT_ORDER', 'REVENUE_SHARE', 'EQUAL'));
+
 DROP INDEX IF EXISTS idx_finops_cost_allocations_uniqueness;
 CREATE UNIQUE INDEX IF NOT EXISTS idx_finops_cost_allocations_uniqueness
     ON finops_cost_allocations(
+        namespace,
         period_start,
         period_end,
         source_type,
@@ -741,13 +1330,51 @@ CREATE TABLE IF NOT EXISTS finops_period_reconciliations (
     status TEXT NOT NULL CHECK (status IN ('BALANCED', 'OUT_OF_TOLERANCE', 'NO_SOURCE_COSTS')),
     completed_by_agent_id TEXT NOT NULL,
     completed_at TIMESTAMPTZ NOT NULL,
-    PRIMARY KEY (period_start, period_end),
+    namespace TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY (namespace, period_start, period_end),
     CHECK (period_end > period_start)
 );
 
+ALTER TABLE finops_period_reconciliations ADD COLUMN IF NOT EXISTS namespace TEXT NOT NULL DEFAULT '';
+ALTER TABLE finops_period_reconciliations
+    ADD COLUMN IF NOT EXISTS allocation_basis TEXT NOT NULL DEFAULT 'REVENUE_SHARE'
+    CHECK (allocation_basis IN ('REVENUE_SHARE', 'EQUAL', 'DIRECT_ONLY'));
+
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1
+        FROM pg_index i
+        INNER JOIN pg_class c ON c.oid = i.indexrelid
+        WHERE c.relname = 'finops_period_reconciliations_pkey'
+          AND i.indnatts = 2
+    ) THEN
+        ALTER TABLE finops_period_reconciliations
+            DROP CONSTRAINT finops_period_reconciliations_pkey;
+        ALTER TABLE finops_period_reconciliations
+            ADD PRIMARY KEY (namespace, period_start, period_end);
+    END IF;
+END
+$$;
+
 CREATE INDEX IF NOT EXISTS idx_finops_period_reconciliations_completed_at
     ON finops_period_reconciliations(completed_at DESC);
 
+CREATE TABLE IF NOT EXISTS period_close_log (
+    id UUID PRIMARY KEY,
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('CLOSED', 'BLOCKED')),
+    steps_json JSONB NOT NULL,
+    escalation_id UUID REFERENCES governance_escalations(id),
+    created_by_agent_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    CHECK (period_end >= period_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_period_close_log_period
+    ON period_close_log(period_end DESC, created_at DESC);
+
 CREATE TABLE IF NOT EXISTS skill_registry (
     id UUID PRIMARY KEY,
     skill_id TEXT NOT NULL,
@@ -762,6 +1389,19 @@ CREATE TABLE IF NOT EXISTS skill_registry (
     UNIQUE (skill_id, skill_version)
 );
 
+ALTER TABLE skill_registry ADD COLUMN IF NOT EXISTS status_changed_by_agent_id TEXT;
+ALTER TABLE skill_registry ADD COLUMN IF NOT EXISTS revoke_reason TEXT;
+ALTER TABLE skill_registry ADD COLUMN IF NOT EXISTS deprecated_at TIMESTAMPTZ;
+ALTER TABLE skill_registry ADD COLUMN IF NOT EXISTS deprecated_by_agent_id TEXT;
+ALTER TABLE skill_registry ADD COLUMN IF NOT EXISTS deprecation_note TEXT;
+ALTER TABLE skill_registry ADD COLUMN IF NOT EXISTS migration_to_skill_id TEXT;
+ALTER TABLE skill_registry ADD COLUMN IF NOT EXISTS migration_to_skill_version TEXT;
+ALTER TABLE skill_registry ADD COLUMN IF NOT EXISTS superseded_by TEXT;
+ALTER TABLE skill_registry DROP CONSTRAINT IF EXISTS skill_registry_approval_status_check;
+ALTER TABLE skill_registry
+    ADD CONSTRAINT skill_registry_approval_status_check
+    CHECK (approval_status IN ('DRAFT', 'REVIEW', 'APPROVED', 'REVOKED'));
+
 CREATE INDEX IF NOT EXISTS idx_skill_registry_capability_status
     ON skill_registry(capability, approval_status, updated_at DESC);
 
@@ -800,6 +1440,8 @@ CREATE TABLE IF NOT EXISTS skill_invocations (
     created_at TIMESTAMPTZ NOT NULL
 );
 
+ALTER TABLE skill_invocations ALTER COLUMN order_id DROP NOT NULL;
+
 CREATE INDEX IF NOT EXISTS idx_skill_invocations_order_id
     ON skill_invocations(order_id, created_at DESC);
 CREATE INDEX IF NOT EXISTS idx_skill_invocations_skill_version
@@ -911,3 +1553,56 @@ VALUES
         NOW()
     )
 ON CONFLICT (intent, transaction_type) DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS agent_registry (
+    agent_id TEXT PRIMARY KEY,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO agent_registry(agent_id)
+VALUES
+    ('strategy-agent'),
+    ('sales-agent'),
+    ('procurement-agent'),
+    ('warehouse-agent'),
+    ('ar-agent'),
+    ('controller-agent'),
+    ('board-agent'),
+    ('ops-orchestrator-agent'),
+    ('audit-agent'),
+    ('payroll-agent')
+ON CONFLICT (agent_id) DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS agent_permissions (
+    agent_id TEXT NOT NULL REFERENCES agent_registry(agent_id) ON DELETE CASCADE,
+    capability TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (agent_id, capability)
+);
+
+INSERT INTO agent_permissions(agent_id, capability)
+VALUES
+    ('strategy-agent', 'governance:decide'),
+    ('controller-agent', 'governance:decide'),
+    ('controller-agent', 'finops:ingest'),
+    ('controller-agent', 'finops:allocate'),
+    ('controller-agent', 'finops:settle'),
+    ('board-agent', 'governance:decide'),
+    ('board-agent', 'finops:ingest'),
+    ('board-agent', 'finops:allocate'),
+    ('board-agent', 'finops:settle'),
+    ('payroll-agent', 'finops:ingest'),
+    ('payroll-agent', 'finops:allocate'),
+    ('payroll-agent', 'finops:settle'),
+    ('board-agent', 'outbox:dead-letters'),
+    ('strategy-agent', 'outbox:dead-letters'),
+    ('audit-agent', 'memory:audit'),
+    ('board-agent', 'memory:audit'),
+    ('strategy-agent', 'memory:audit'),
+    ('controller-agent', 'memory:audit')
+ON CONFLICT (agent_id, capability) DO NOTHING;
+
+ALTER TABLE agent_registry DROP COLUMN IF EXISTS is_governance_actor;
+ALTER TABLE agent_registry DROP COLUMN IF EXISTS is_finops_actor;